@@ -23,8 +23,8 @@ fn main() {
         }
     }).unwrap();
     println!("Task registered");
-    //let free = freertos_rs_xPortGetFreeHeapSize();
-    // println!("Free Memory: {}!", free);
+    let free = free_heap_size();
+    println!("Free Memory: {}!", free);
     println!("Starting scheduler");
     Scheduler::start();
 }
@@ -4,8 +4,22 @@ use std::process::exit;
 
 use walkdir::WalkDir;
 
-/// Get the heap implementation from `cargo` features.
+/// Get the heap implementation from `cargo` features or the `FREERTOS_HEAP` environment variable.
+///
+/// `FREERTOS_HEAP` can point to a custom heap implementation `.c` file using an absolute
+/// path, since build scripts run in the directory of this package rather than the one
+/// of the application. The `no_heap` feature disables compiling
+/// any heap implementation, e.g. when `configSUPPORT_DYNAMIC_ALLOCATION` is `0`.
 pub fn heap() -> Option<PathBuf> {
+  println!("cargo:rerun-if-env-changed=FREERTOS_HEAP");
+  let custom_heap = env::var_os("FREERTOS_HEAP").map(PathBuf::from);
+  let no_heap = env::var("CARGO_FEATURE_NO_HEAP").is_ok();
+
+  if custom_heap.is_some() && no_heap {
+    eprintln!("`FREERTOS_HEAP` and feature `no_heap` are mutually exclusive.");
+    exit(1);
+  }
+
   let mut heap = None;
 
   for i in 1..=5 {
@@ -19,6 +33,37 @@ pub fn heap() -> Option<PathBuf> {
     }
   }
 
+  if let Some(h) = heap {
+    if custom_heap.is_some() {
+      eprintln!("`FREERTOS_HEAP` and feature `heap_{h}` are mutually exclusive.");
+      exit(1);
+    }
+
+    if no_heap {
+      eprintln!("Features `no_heap` and `heap_{h}` are mutually exclusive.");
+      exit(1);
+    }
+  }
+
+  if let Some(custom_heap) = custom_heap {
+    if !custom_heap.is_absolute() {
+      eprintln!("`FREERTOS_HEAP` must be an absolute path, got `{}`.", custom_heap.display());
+      exit(1);
+    }
+
+    println!("cargo:rerun-if-changed={}", custom_heap.display());
+    return Some(custom_heap)
+  }
+
+  // `heap_3` wraps the C library `malloc`, so it does not track its size.
+  if matches!(heap, Some(1 | 2 | 4 | 5)) {
+    println!(r#"cargo:rustc-cfg=freertos_feature="heap_free_size""#);
+  }
+
+  if matches!(heap, Some(4 | 5)) {
+    println!(r#"cargo:rustc-cfg=freertos_feature="heap_stats""#);
+  }
+
   heap.map(|heap| format!("heap_{}.c", heap).into())
 }
 
@@ -32,6 +77,7 @@ pub fn port() -> PathBuf {
     ("thumbv7em-none-eabihf", _) => Path::new("GCC").join("ARM_CM4F"),
     ("thumbv8m.main-none-eabi", _) => Path::new("GCC").join("ARM_CM33_NTZ").join("non_secure"),
     ("thumbv8m.main-none-eabihf", _) => Path::new("GCC").join("ARM_CM33_NTZ").join("non_secure"),
+    (target, _) if target.starts_with("riscv32") => Path::new("GCC").join("RISC-V"),
     (_, "unix") => Path::new("ThirdParty").join("GCC").join("Posix"),
     (_, "windows") => PathBuf::from("MSVC-MingW"),
     _ => {
@@ -41,6 +87,30 @@ pub fn port() -> PathBuf {
   }
 }
 
+/// Whether the target uses the RISC-V port.
+fn is_riscv() -> bool {
+  env::var("CARGO_CFG_TARGET_ARCH").map(|arch| arch == "riscv32").unwrap_or(false)
+}
+
+/// Get additional include directories and assembly files required by the port.
+///
+/// The RISC-V port needs a chip specific extensions header, selected by
+/// `FREERTOS_RISCV_CHIP_EXTENSIONS`, which defaults to `RISCV_MTIME_CLINT_no_extensions`.
+fn port_extras(port: &Path) -> (Vec<PathBuf>, Vec<PathBuf>) {
+  if !is_riscv() {
+    return (vec![], vec![])
+  }
+
+  println!("cargo:rerun-if-env-changed=FREERTOS_RISCV_CHIP_EXTENSIONS");
+  let extensions = env::var("FREERTOS_RISCV_CHIP_EXTENSIONS")
+    .unwrap_or_else(|_| "RISCV_MTIME_CLINT_no_extensions".into());
+
+  let includes = vec![port.join("chip_specific_extensions").join(extensions)];
+  let asm_files = vec![port.join("portASM.S")];
+
+  (includes, asm_files)
+}
+
 /// Find `.c` files until the given depth.
 pub fn find_c_files(dir: impl AsRef<Path>, depth: Option<usize>) -> Result<Vec<PathBuf>, std::io::Error> {
   let mut w = WalkDir::new(dir).follow_links(false);
@@ -84,11 +154,15 @@ pub fn builders(
     c_files.push(heap);
   }
 
-  let includes = vec![
+  let (port_includes, asm_files) = port_extras(&port);
+  c_files.extend(asm_files);
+
+  let mut includes = vec![
     include,
     port,
     config.into(),
   ];
+  includes.extend(port_includes);
 
   let mut cc = cc::Build::new();
   let mut bindgen = bindgen::builder()
@@ -20,6 +20,22 @@ impl ParseCallbacks for Callbacks {
         println!(r#"cargo:rustc-cfg=freertos_feature="timer_get_period""#);
         return None
       }
+      "vTaskGetRunTimeStatistics" => {
+        println!(r#"cargo:rustc-cfg=freertos_feature="run_time_statistics""#);
+        return None
+      }
+      "xTaskDelayUntil" => {
+        println!(r#"cargo:rustc-cfg=freertos_feature="task_delay_until""#);
+        return None
+      }
+      "xTimerGetExpiryTime" => {
+        println!(r#"cargo:rustc-cfg=freertos_feature="timer_get_expiry_time""#);
+        return None
+      }
+      "xStreamBufferResetFromISR" => {
+        println!(r#"cargo:rustc-cfg=freertos_feature="stream_buffer_reset_from_isr""#);
+        return None
+      }
       _ => return None
     }.to_owned())
   }
@@ -33,6 +49,42 @@ impl ParseCallbacks for Callbacks {
       println!(r#"cargo:rustc-cfg=freertos_feature="static_allocation""#);
     }
 
+    if name == "configUSE_TRACE_FACILITY" && value != 0 {
+      println!(r#"cargo:rustc-cfg=freertos_feature="trace_facility""#);
+    }
+
+    if name == "configGENERATE_RUN_TIME_STATS" && value != 0 {
+      println!(r#"cargo:rustc-cfg=freertos_feature="run_time_stats""#);
+    }
+
+    if name == "configUSE_STATS_FORMATTING_FUNCTIONS" && value > 0 {
+      println!(r#"cargo:rustc-cfg=freertos_feature="stats_formatting""#);
+    }
+
+    if name == "configUSE_QUEUE_SETS" && value != 0 {
+      println!(r#"cargo:rustc-cfg=freertos_feature="queue_sets""#);
+    }
+
+    if name == "INCLUDE_xTaskAbortDelay" && value != 0 {
+      println!(r#"cargo:rustc-cfg=freertos_feature="task_abort_delay""#);
+    }
+
+    if name == "INCLUDE_xTimerPendFunctionCall" && value != 0 {
+      println!(r#"cargo:rustc-cfg=freertos_feature="timer_pend_function_call""#);
+    }
+
+    if name == "configNUM_THREAD_LOCAL_STORAGE_POINTERS" && value > 0 {
+      println!(r#"cargo:rustc-cfg=freertos_feature="local_storage""#);
+    }
+
+    if name == "configNUMBER_OF_CORES" && value > 1 {
+      println!(r#"cargo:rustc-cfg=freertos_feature="smp""#);
+    }
+
+    if name == "configUSE_CORE_AFFINITY" && value != 0 {
+      println!(r#"cargo:rustc-cfg=freertos_feature="core_affinity""#);
+    }
+
     match name {
       "configMAX_PRIORITIES" => Some(IntKind::U8),
       "configTIMER_TASK_STACK_DEPTH" => Some(IntKind::U16),
@@ -55,6 +107,11 @@ impl ParseCallbacks for Callbacks {
       "uxPriority" | "uxTopPriority" | "uxReadyPriorities" => syn::parse_quote! { UBaseType_t },
       "xQueue" => syn::parse_quote! { QueueHandle_t },
       "xMutex" | "xSemaphore" => syn::parse_quote! { SemaphoreHandle_t },
+      "xEventGroup" => syn::parse_quote! { EventGroupHandle_t },
+      "uxBitsToSet" | "uxBitsToClear" => syn::parse_quote! { EventBits_t },
+      "xStreamBufferSizeBytes" | "xTriggerLevelBytes" | "xBufferSizeBytes" | "xDataLengthBytes" => syn::parse_quote! { usize },
+      "xStreamBuffer" => syn::parse_quote! { StreamBufferHandle_t },
+      "xMessageBuffer" => syn::parse_quote! { MessageBufferHandle_t },
       // "xBlockTime" | "xTicksToWait" | "xNewPeriod" | "xExpectedIdleTime" | "xTimeIncrement" => "TickType_t",
       // "xTask" | "xTaskToNotify" => "TaskHandle_t",
       // "pxCreatedTask" => "*mut TaskHandle_t",
@@ -113,6 +170,15 @@ fn main() {
 
   cc.file(shim_dir.join("shim.c"));
 
+  // Additional sources, e.g. port overrides or vendor HAL shims, separated like `PATH`.
+  println!("cargo:rerun-if-env-changed=FREERTOS_EXTRA_SOURCES");
+  if let Some(extra_sources) = env::var_os("FREERTOS_EXTRA_SOURCES") {
+    for source in env::split_paths(&extra_sources) {
+      println!("cargo:rerun-if-changed={}", source.display());
+      cc.file(source);
+    }
+  }
+
   if let Err(err) = cc.try_compile("freertos") {
     eprintln!("Compilation failed: {}", err);
     exit(1);
@@ -1,9 +1,125 @@
 //! FreeRTOS allocator and allocation types.
 
 use core::alloc::{GlobalAlloc, Layout};
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::shim::{pvPortMalloc, vPortFree};
 
+/// Whether FreeRTOS was configured with `configSUPPORT_DYNAMIC_ALLOCATION`.
+pub const ALLOCATION_SUPPORTS_DYNAMIC: bool = cfg!(freertos_feature = "dynamic_allocation");
+
+/// Whether FreeRTOS was configured with `configSUPPORT_STATIC_ALLOCATION`.
+pub const ALLOCATION_SUPPORTS_STATIC: bool = cfg!(freertos_feature = "static_allocation");
+
+/// Allocation mode for FreeRTOS objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocationMode {
+  /// Objects are allocated on the FreeRTOS heap.
+  Dynamic,
+  /// Objects are allocated in user-provided memory.
+  Static,
+}
+
+impl AllocationMode {
+  /// Check whether this allocation mode is supported.
+  pub const fn is_supported(self) -> bool {
+    match self {
+      Self::Dynamic => ALLOCATION_SUPPORTS_DYNAMIC,
+      Self::Static => ALLOCATION_SUPPORTS_STATIC,
+    }
+  }
+}
+
+/// Assert that the given allocation `mode` is supported.
+///
+/// When used in a `const` context, this produces a compile error if the
+/// allocation mode is not supported by the FreeRTOS configuration.
+///
+/// # Examples
+///
+/// ```
+/// use freertos_rust::alloc::{require_allocation, AllocationMode};
+///
+/// const _: () = require_allocation(AllocationMode::Dynamic);
+/// ```
+pub const fn require_allocation(mode: AllocationMode) {
+  if !mode.is_supported() {
+    match mode {
+      AllocationMode::Dynamic => panic!("dynamic allocation is not supported, enable `configSUPPORT_DYNAMIC_ALLOCATION`"),
+      AllocationMode::Static => panic!("static allocation is not supported, enable `configSUPPORT_STATIC_ALLOCATION`"),
+    }
+  }
+}
+
+/// Get the amount of free heap memory in bytes.
+///
+/// This requires the `heap_1`, `heap_2`, `heap_4` or `heap_5` feature.
+///
+/// # Examples
+///
+/// ```
+/// let free = freertos_rust::free_heap_size();
+/// println!("Free heap: {free} bytes");
+/// ```
+#[cfg(freertos_feature = "heap_free_size")]
+#[inline]
+pub fn free_heap_size() -> usize {
+  unsafe { crate::shim::xPortGetFreeHeapSize() as usize }
+}
+
+/// Get the lowest amount of free heap memory in bytes since the system booted.
+///
+/// This requires the `heap_4` or `heap_5` feature.
+#[cfg(freertos_feature = "heap_stats")]
+#[inline]
+pub fn minimum_ever_free_heap_size() -> usize {
+  unsafe { crate::shim::xPortGetMinimumEverFreeHeapSize() as usize }
+}
+
+/// Heap statistics returned by [`heap_stats`].
+#[cfg(freertos_feature = "heap_stats")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HeapStats {
+  /// The total amount of free heap memory in bytes.
+  pub available_heap_space: usize,
+  /// The size of the largest free block in bytes.
+  pub largest_free_block: usize,
+  /// The size of the smallest free block in bytes.
+  pub smallest_free_block: usize,
+  /// The number of free blocks.
+  pub free_blocks: usize,
+  /// The lowest amount of free heap memory in bytes since the system booted.
+  pub minimum_ever_free: usize,
+  /// The number of successful allocations.
+  pub successful_allocations: usize,
+  /// The number of successful frees.
+  pub successful_frees: usize,
+}
+
+/// Get the current heap statistics.
+///
+/// This requires the `heap_4` or `heap_5` feature.
+#[cfg(freertos_feature = "heap_stats")]
+pub fn heap_stats() -> HeapStats {
+  let mut stats = core::mem::MaybeUninit::<crate::shim::HeapStats_t>::uninit();
+
+  let stats = unsafe {
+    crate::shim::vPortGetHeapStats(stats.as_mut_ptr());
+    stats.assume_init()
+  };
+
+  HeapStats {
+    available_heap_space: stats.xAvailableHeapSpaceInBytes as usize,
+    largest_free_block: stats.xSizeOfLargestFreeBlockInBytes as usize,
+    smallest_free_block: stats.xSizeOfSmallestFreeBlockInBytes as usize,
+    free_blocks: stats.xNumberOfFreeBlocks as usize,
+    minimum_ever_free: stats.xMinimumEverFreeBytesRemaining as usize,
+    successful_allocations: stats.xNumberOfSuccessfulAllocations as usize,
+    successful_frees: stats.xNumberOfSuccessfulFrees as usize,
+  }
+}
+
 /// An allocator based on the FreeRTOS Memory Management API.
 ///
 /// The actual implementation on which `heap_*` feature is enabled.
@@ -28,6 +144,71 @@ unsafe impl GlobalAlloc for Allocator {
   }
 }
 
+/// An [`Allocator`] which keeps track of the number of bytes allocated through it.
+///
+/// Only allocations made through this allocator are tracked, so comparing these
+/// counters with the FreeRTOS heap statistics shows how much of the heap is used
+/// by Rust and how much by FreeRTOS itself and allocator overhead.
+///
+/// # Usage
+///
+/// ```
+/// use freertos_rust::alloc::TrackingAllocator;
+///
+/// #[global_allocator]
+/// static ALLOC: TrackingAllocator = TrackingAllocator::new();
+///
+/// let v = vec![0u8; 32];
+/// assert!(ALLOC.bytes_in_use() >= 32);
+/// assert!(ALLOC.peak_bytes() >= ALLOC.bytes_in_use());
+/// ```
+#[derive(Debug, Default)]
+pub struct TrackingAllocator {
+  bytes_in_use: AtomicUsize,
+  peak_bytes: AtomicUsize,
+}
+
+impl TrackingAllocator {
+  /// Create a new tracking allocator.
+  pub const fn new() -> Self {
+    Self {
+      bytes_in_use: AtomicUsize::new(0),
+      peak_bytes: AtomicUsize::new(0),
+    }
+  }
+
+  /// Get the number of bytes currently allocated.
+  #[inline]
+  pub fn bytes_in_use(&self) -> usize {
+    self.bytes_in_use.load(Ordering::Relaxed)
+  }
+
+  /// Get the highest number of bytes allocated at the same time.
+  #[inline]
+  pub fn peak_bytes(&self) -> usize {
+    self.peak_bytes.load(Ordering::Relaxed)
+  }
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    let ptr = Allocator.alloc(layout);
+
+    if !ptr.is_null() {
+      let size = layout.size();
+      let bytes_in_use = self.bytes_in_use.fetch_add(size, Ordering::Relaxed) + size;
+      self.peak_bytes.fetch_max(bytes_in_use, Ordering::Relaxed);
+    }
+
+    ptr
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    Allocator.dealloc(ptr, layout);
+    self.bytes_in_use.fetch_sub(layout.size(), Ordering::Relaxed);
+  }
+}
+
 #[export_name = "vApplicationMallocFailedHook"]
 extern "C" fn malloc_failed_hook() {
   panic!("`malloc` failed");
@@ -5,9 +5,101 @@ use core::{
   str,
   sync::atomic::{AtomicPtr, Ordering},
 };
+#[cfg(freertos_feature = "dynamic_allocation")]
+use core::cell::UnsafeCell;
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+use alloc2::{boxed::Box, vec::Vec};
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+use crate::shim::{freertos_rs_enter_critical_from_isr, freertos_rs_exit_critical_from_isr};
 
 static ASSERT_FUNCTION: AtomicPtr<()> = AtomicPtr::new(assert_panic as *mut _);
 
+#[cfg(freertos_feature = "dynamic_allocation")]
+type BoxAssertFn = Box<dyn Fn(&'static str, &'static str, usize) + Send + Sync>;
+
+/// State of the boxed assertion handler.
+#[cfg(freertos_feature = "dynamic_allocation")]
+struct BoxedHandler {
+  /// The current handler.
+  current: Option<BoxAssertFn>,
+  /// Number of running calls of the current or a retired handler.
+  calls: usize,
+  /// Replaced handlers which may still be running.
+  retired: Vec<BoxAssertFn>,
+}
+
+/// The boxed assertion handler, protected by a critical section.
+///
+/// Since an assertion may fail in an interrupt, a FreeRTOS mutex cannot be used here.
+#[cfg(freertos_feature = "dynamic_allocation")]
+struct BoxedHandlerLock(UnsafeCell<BoxedHandler>);
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+unsafe impl Sync for BoxedHandlerLock {}
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+impl BoxedHandlerLock {
+  /// Run `f` with exclusive access to the handler state.
+  fn with<R>(&self, f: impl FnOnce(&mut BoxedHandler) -> R) -> R {
+    unsafe {
+      let saved_interrupt_status = freertos_rs_enter_critical_from_isr();
+      let res = f(&mut *self.0.get());
+      freertos_rs_exit_critical_from_isr(saved_interrupt_status);
+      res
+    }
+  }
+
+  /// Replace the current handler.
+  ///
+  /// The replaced handler is dropped immediately if no call is running. Otherwise, it is
+  /// retired and dropped by the next call to `replace` after all running calls returned.
+  /// Since assertions may fail in an interrupt, handlers are never dropped by `call`.
+  fn replace(&self, f: Option<BoxAssertFn>) {
+    let unreachable = self.with(|handler| {
+      let old = mem::replace(&mut handler.current, f);
+
+      if handler.calls == 0 {
+        (old, mem::take(&mut handler.retired))
+      } else {
+        handler.retired.extend(old);
+        (None, Vec::new())
+      }
+    });
+
+    // Drop outside of the critical section.
+    drop(unreachable);
+  }
+
+  /// Call the current handler, if any.
+  ///
+  /// Returns `false` if no handler is set.
+  fn call(&self, message: &'static str, file_name: &'static str, line: usize) -> bool {
+    let f = self.with(|handler| {
+      let f: *const BoxAssertFn = handler.current.as_ref()?;
+      handler.calls += 1;
+      Some(f)
+    });
+
+    let Some(f) = f else { return false };
+
+    // SAFETY: The handler is not dropped while `calls` is non-zero.
+    unsafe { (*f)(message, file_name, line) };
+
+    self.with(|handler| handler.calls -= 1);
+
+    true
+  }
+}
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+static BOXED_ASSERT_FUNCTION: BoxedHandlerLock = BoxedHandlerLock(UnsafeCell::new(BoxedHandler {
+  current: None,
+  calls: 0,
+  retired: Vec::new(),
+}));
+
 /// Set a custom assertion handler.
 ///
 /// The handler receives the message (i.e. the literal boolean expression as it appears in C code),
@@ -24,6 +116,35 @@ static ASSERT_FUNCTION: AtomicPtr<()> = AtomicPtr::new(assert_panic as *mut _);
 /// ```
 pub fn set_assertion_handler(f: fn(message: &'static str, file_name: &'static str, line: usize)) {
   ASSERT_FUNCTION.store(f as *mut _, Ordering::Release);
+
+  #[cfg(freertos_feature = "dynamic_allocation")]
+  BOXED_ASSERT_FUNCTION.replace(None);
+}
+
+/// Set a custom assertion handler which can capture state, e.g. a logger.
+///
+/// This takes precedence over a handler set using [`set_assertion_handler`] until
+/// that function is called again.
+///
+/// A previously set handler is dropped, unless it is currently running due to an assertion
+/// failing concurrently in a different task or interrupt. In that case, it is dropped by the
+/// next call to this function or [`set_assertion_handler`] instead.
+///
+/// # Examples
+///
+/// ```
+/// extern crate alloc;
+/// use alloc::boxed::Box;
+///
+/// let prefix = "FreeRTOS";
+///
+/// freertos_rust::set_boxed_assertion_handler(Box::new(move |message, file_name, line| {
+///   panic!("{} assertion in file {} at line {} failed: {}", prefix, file_name, line, message);
+/// }));
+/// ```
+#[cfg(freertos_feature = "dynamic_allocation")]
+pub fn set_boxed_assertion_handler(f: Box<dyn Fn(&'static str, &'static str, usize) + Send + Sync>) {
+  BOXED_ASSERT_FUNCTION.replace(Some(f));
 }
 
 fn assert_panic(message: &'static str, file_name: &'static str, line: usize) {
@@ -45,6 +166,11 @@ extern "C" fn assert_called(
       str::from_utf8_unchecked(slice::from_raw_parts(file_name.cast(), file_name_len))
     };
 
+    #[cfg(freertos_feature = "dynamic_allocation")]
+    if BOXED_ASSERT_FUNCTION.call(message, file_name, line) {
+      return
+    }
+
     let f: fn(&'static str, &'static str, usize) = unsafe { mem::transmute(ASSERT_FUNCTION.load(Ordering::Acquire)) };
     f(message, file_name, line);
 }
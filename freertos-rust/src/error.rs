@@ -13,16 +13,30 @@ pub enum FreeRtosError {
   QueueFull,
   /// Task does not exist.
   TaskNotFound,
+  /// An invalid parameter was passed.
+  InvalidParameter,
+  /// The receiving side has been dropped.
+  Disconnected,
+  /// The operation was cancelled.
+  Cancelled,
+  /// Received data could not be decoded.
+  InvalidData,
 }
 
 impl fmt::Display for FreeRtosError {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
       Self::OutOfMemory => "out of memory",
-      Self::Timeout => "timed out",
-      Self::Unavailable => "unavailable",
-      Self::QueueFull => "queue full",
+      Self::Timeout => "operation timed out",
+      Self::Unavailable => "resource unavailable",
+      Self::QueueFull => "queue is full",
       Self::TaskNotFound => "task not found",
+      Self::InvalidParameter => "invalid parameter",
+      Self::Disconnected => "disconnected",
+      Self::Cancelled => "operation cancelled",
+      Self::InvalidData => "invalid data",
     }.fmt(f)
   }
 }
+
+impl core::error::Error for FreeRtosError {}
@@ -12,6 +12,24 @@ pub type SemaphoreHandle_t = crate::shim::SemaphoreHandle_t;
 /// Raw queue handle.
 pub type QueueHandle_t = crate::shim::QueueHandle_t;
 
+/// Raw event group handle.
+pub type EventGroupHandle_t = crate::shim::EventGroupHandle_t;
+
+/// Event group bits type.
+pub type EventBits_t = crate::shim::EventBits_t;
+
+/// Raw message buffer handle.
+pub type MessageBufferHandle_t = crate::shim::MessageBufferHandle_t;
+
+/// Raw stream buffer handle.
+pub type StreamBufferHandle_t = crate::shim::StreamBufferHandle_t;
+
+/// Raw queue set handle.
+pub type QueueSetHandle_t = crate::shim::QueueSetHandle_t;
+
+/// Raw handle of a queue set member.
+pub type QueueSetMemberHandle_t = crate::shim::QueueSetMemberHandle_t;
+
 /// Raw tick type.
 pub type TickType_t = crate::shim::TickType_t;
 
@@ -21,6 +39,12 @@ pub type TimerHandle_t = crate::shim::TimerHandle_t;
 /// Raw task handle.
 pub type TaskHandle_t = crate::shim::TaskHandle_t;
 
+/// Stack word type.
+pub type StackType_t = crate::shim::StackType_t;
+
+/// Buffer type for a statically allocated task.
+pub type StaticTask_t = crate::shim::StaticTask_t;
+
 /// Helper trait to get the pointee type.
 pub(crate) trait PtrType {
   type Type;
@@ -20,6 +20,9 @@ extern void vAssertCalled(
 #include "timers.h"
 #include "queue.h"
 #include "semphr.h"
+#include "event_groups.h"
+#include "stream_buffer.h"
+#include "message_buffer.h"
 
 uint8_t freertos_rs_sizeof(uint8_t _type) {
 	switch (_type) {
@@ -84,6 +87,30 @@ void freertos_rs_yield_from_isr(BaseType_t x) {
 	portYIELD_FROM_ISR(x);
 }
 
+void freertos_rs_yield() {
+	taskYIELD();
+}
+
+#if defined(configNUMBER_OF_CORES) && configNUMBER_OF_CORES > 1
+BaseType_t freertos_rs_get_core_id() {
+	return portGET_CORE_ID();
+}
+
+#if configGENERATE_RUN_TIME_STATS == 1
+typedef configRUN_TIME_COUNTER_TYPE freertos_rs_run_time_counter_t;
+
+freertos_rs_run_time_counter_t freertos_rs_get_run_time_counter() {
+#ifdef portALT_GET_RUN_TIME_COUNTER_VALUE
+	freertos_rs_run_time_counter_t value;
+	portALT_GET_RUN_TIME_COUNTER_VALUE(value);
+	return value;
+#else
+	return portGET_RUN_TIME_COUNTER_VALUE();
+#endif
+}
+#endif
+#endif
+
 #ifndef configTASK_NOTIFICATION_ARRAY_ENTRIES
 #define configTASK_NOTIFICATION_ARRAY_ENTRIES 1
 
@@ -96,6 +123,21 @@ BaseType_t freertos_rs_task_notify_indexed_from_isr(TaskHandle_t task, UBaseType
   (void)index;
 	return xTaskNotifyFromISR(task, value, eAction, xHigherPriorityTaskWoken);
 }
+
+BaseType_t freertos_rs_task_notify_wait_indexed(UBaseType_t index, uint32_t clear_on_entry, uint32_t clear_on_exit, uint32_t* value, TickType_t ticks_to_wait) {
+  (void)index;
+	return xTaskNotifyWait(clear_on_entry, clear_on_exit, value, ticks_to_wait);
+}
+
+uint32_t freertos_rs_task_notify_take_indexed(UBaseType_t index, BaseType_t clear_on_exit, TickType_t ticks_to_wait) {
+  (void)index;
+	return ulTaskNotifyTake(clear_on_exit, ticks_to_wait);
+}
+
+void freertos_rs_task_notify_give_indexed(TaskHandle_t task, UBaseType_t index) {
+  (void)index;
+	xTaskNotifyGive(task);
+}
 #else
 BaseType_t freertos_rs_task_notify_indexed(TaskHandle_t task, UBaseType_t index, uint32_t value, eNotifyAction eAction) {
 	return xTaskNotifyIndexed(task, index, value, eAction);
@@ -104,8 +146,28 @@ BaseType_t freertos_rs_task_notify_indexed(TaskHandle_t task, UBaseType_t index,
 BaseType_t freertos_rs_task_notify_indexed_from_isr(TaskHandle_t task, UBaseType_t index, uint32_t value, eNotifyAction eAction, BaseType_t* xHigherPriorityTaskWoken) {
 	return xTaskNotifyIndexedFromISR(task, index, value, eAction, xHigherPriorityTaskWoken);
 }
+
+BaseType_t freertos_rs_task_notify_wait_indexed(UBaseType_t index, uint32_t clear_on_entry, uint32_t clear_on_exit, uint32_t* value, TickType_t ticks_to_wait) {
+	return xTaskNotifyWaitIndexed(index, clear_on_entry, clear_on_exit, value, ticks_to_wait);
+}
+
+uint32_t freertos_rs_task_notify_take_indexed(UBaseType_t index, BaseType_t clear_on_exit, TickType_t ticks_to_wait) {
+	return ulTaskNotifyTakeIndexed(index, clear_on_exit, ticks_to_wait);
+}
+
+void freertos_rs_task_notify_give_indexed(TaskHandle_t task, UBaseType_t index) {
+	xTaskNotifyGiveIndexed(task, index);
+}
 #endif
 
+BaseType_t freertos_rs_task_notify_and_query(TaskHandle_t task, uint32_t value, eNotifyAction eAction, uint32_t* previous_value) {
+	return xTaskNotifyAndQuery(task, value, eAction, previous_value);
+}
+
+BaseType_t freertos_rs_task_notify_and_query_from_isr(TaskHandle_t task, uint32_t value, eNotifyAction eAction, uint32_t* previous_value, BaseType_t* xHigherPriorityTaskWoken) {
+	return xTaskNotifyAndQueryFromISR(task, value, eAction, previous_value, xHigherPriorityTaskWoken);
+}
+
 void freertos_rs_enter_critical() {
 	taskENTER_CRITICAL();
 }
@@ -113,3 +175,11 @@ void freertos_rs_enter_critical() {
 void freertos_rs_exit_critical() {
 	taskEXIT_CRITICAL();
 }
+
+UBaseType_t freertos_rs_enter_critical_from_isr() {
+	return taskENTER_CRITICAL_FROM_ISR();
+}
+
+void freertos_rs_exit_critical_from_isr(UBaseType_t saved_interrupt_status) {
+	taskEXIT_CRITICAL_FROM_ISR(saved_interrupt_status);
+}
@@ -52,3 +52,30 @@ extern "C" fn application_idle_hook() {
   let f: fn() = unsafe { mem::transmute(IDLE_FUNCTION.load(Ordering::Acquire)) };
   f();
 }
+
+static NO_TASKS_FUNCTION: AtomicPtr<()> = AtomicPtr::new(default_no_tasks_hook as *mut _);
+
+/// Set a hook which is called in debug builds when the scheduler is started without any tasks.
+///
+/// This usually means that creating the tasks was forgotten or failed, but is valid
+/// for applications only driven by timers or interrupts, so nothing happens by default.
+///
+/// # Examples
+///
+/// ```
+/// fn my_no_tasks_hook() {
+///   // Log a warning.
+/// }
+///
+/// freertos_rust::set_no_tasks_hook(my_no_tasks_hook);
+/// ```
+pub fn set_no_tasks_hook(f: fn()) {
+  NO_TASKS_FUNCTION.store(f as *mut _, Ordering::Release);
+}
+
+fn default_no_tasks_hook() {}
+
+pub(crate) fn no_tasks_hook() {
+  let f: fn() = unsafe { mem::transmute(NO_TASKS_FUNCTION.load(Ordering::Acquire)) };
+  f();
+}
@@ -1,7 +1,13 @@
 use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+use core::ops::Deref;
 
-use crate::shim::freertos_rs_yield_from_isr;
-use crate::ffi::BaseType_t;
+use crate::shim::{freertos_rs_yield_from_isr, freertos_rs_enter_critical_from_isr, freertos_rs_exit_critical_from_isr, pdFALSE, pdTRUE};
+use crate::ffi::{BaseType_t, UBaseType_t};
+#[cfg(feature = "sync")]
+use crate::sync::{QueueHandle, SemaphoreHandle};
+use crate::task::{TaskHandle, TaskNotification};
+use crate::FreeRtosError;
 
 /// Representation of an interrupt context.
 ///
@@ -12,6 +18,11 @@ use crate::ffi::BaseType_t;
 ///
 /// A single `InterruptContext` should be created at the start of an interrupt routine and dropped
 /// as the last thing inside the same interrupt routine as dropping it calls `taskYIELD_FROM_ISR`.
+///
+/// All `*_from_isr` calls using the same context accumulate into a single flag: Once any call
+/// has woken a higher priority task, the flag stays set until the context is dropped, so only
+/// a single yield is requested at the end of the interrupt routine, regardless of the number
+/// or order of calls.
 #[repr(transparent)]
 #[must_use]
 pub struct InterruptContext {
@@ -48,6 +59,39 @@ impl InterruptContext {
   pub const fn as_ptr(&self) -> *mut BaseType_t {
     self.x_higher_priority_task_woken.get()
   }
+
+  /// Check whether any `*_from_isr` call using this context woke a task with a higher
+  /// priority than the interrupted task, i.e. whether a context switch is needed.
+  #[inline]
+  pub fn should_yield(&self) -> bool {
+    unsafe { *self.as_ptr() != pdFALSE }
+  }
+
+  /// Consume this context without yielding and return whether a yield is needed.
+  ///
+  /// This is useful if the interrupt routine needs to request the context switch
+  /// itself, e.g. using a port-specific mechanism.
+  #[inline]
+  pub fn into_should_yield(self) -> bool {
+    let should_yield = self.should_yield();
+    core::mem::forget(self);
+    should_yield
+  }
+
+  /// Call `f` with a separate "higher priority task woken" flag and accumulate it into this context.
+  ///
+  /// Returns the result of `f` together with whether this single operation woke a higher priority task.
+  pub(crate) fn track_woken<R>(&self, f: impl FnOnce(*mut BaseType_t) -> R) -> (R, bool) {
+    let mut woken: BaseType_t = pdFALSE;
+    let res = f(&mut woken);
+
+    let woken = woken != pdFALSE;
+    if woken {
+      unsafe { *self.as_ptr() = pdTRUE };
+    }
+
+    (res, woken)
+  }
 }
 
 impl Drop for InterruptContext {
@@ -55,3 +99,165 @@ impl Drop for InterruptContext {
     unsafe { freertos_rs_yield_from_isr(*self.x_higher_priority_task_woken.get_mut()) }
   }
 }
+
+/// A critical section inside an interrupt service routine.
+///
+/// Entering the critical section masks interrupts up to `configMAX_SYSCALL_INTERRUPT_PRIORITY`
+/// and saves the previous interrupt mask, which is restored when the guard is dropped. Since
+/// every guard restores exactly the mask it saved, critical sections can be nested as long
+/// as the guards are dropped in reverse order, which the borrow on the [`InterruptContext`]
+/// and Rust's drop order ensure for guards in the same scope.
+///
+/// # Examples
+///
+/// ```no_run
+/// use freertos_rust::{InterruptContext, IsrCriticalSection};
+///
+/// fn isr() {
+///   let ic = InterruptContext::new();
+///
+///   {
+///     let _cs = IsrCriticalSection::enter(&ic);
+///     // ...
+///   }
+/// }
+/// ```
+#[must_use = "critical section is exited immediately if unused"]
+pub struct IsrCriticalSection<'ic> {
+  saved_interrupt_status: UBaseType_t,
+  _ic: PhantomData<&'ic InterruptContext>,
+}
+
+impl<'ic> IsrCriticalSection<'ic> {
+  /// Enter a critical section from an interrupt service routine.
+  #[inline]
+  pub fn enter(_ic: &'ic InterruptContext) -> Self {
+    let saved_interrupt_status = unsafe { freertos_rs_enter_critical_from_isr() };
+    Self { saved_interrupt_status, _ic: PhantomData }
+  }
+}
+
+impl Drop for IsrCriticalSection<'_> {
+  #[inline]
+  fn drop(&mut self) {
+    unsafe { freertos_rs_exit_critical_from_isr(self.saved_interrupt_status) }
+  }
+}
+
+/// An interrupt service routine scope servicing multiple kernel objects.
+///
+/// An `IsrScope` is a thin wrapper around the [`InterruptContext`] of an interrupt routine,
+/// providing shorthands for common `*_from_isr` calls. It dereferences to the context, so
+/// any other `*_from_isr` function can be called with it as well. Since the wrapped context
+/// yields when it is dropped, `portYIELD_FROM_ISR` is called exactly once at the end of
+/// the scope.
+///
+/// # Examples
+///
+/// ```no_run
+/// use freertos_rust::{IsrScope, sync::{Queue, Semaphore, Binary}};
+///
+/// fn isr(queue: &Queue<u8, 8>, semaphore: &Semaphore<Binary>) {
+///   let scope = IsrScope::new();
+///
+///   let _ = scope.queue_send(queue, 42);
+///   let _ = scope.semaphore_give(semaphore);
+///
+///   // `portYIELD_FROM_ISR` is called once here.
+/// }
+/// ```
+#[repr(transparent)]
+#[must_use]
+pub struct IsrScope {
+  ic: InterruptContext,
+}
+
+impl Deref for IsrScope {
+  type Target = InterruptContext;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.ic
+  }
+}
+
+impl IsrScope {
+  /// Enter a new interrupt service routine scope.
+  ///
+  /// This must be called from within an interrupt service routine.
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    Self { ic: InterruptContext::new() }
+  }
+
+  /// Get the underlying [`InterruptContext`] for calling other `*_from_isr` functions.
+  #[inline]
+  pub fn context(&self) -> &InterruptContext {
+    &self.ic
+  }
+
+  /// Send an item to the end of the queue.
+  ///
+  /// See [`QueueHandle::send_from_isr`](crate::sync::QueueHandle::send_from_isr).
+  #[cfg(feature = "sync")]
+  #[inline]
+  pub fn queue_send<T: Send>(&self, queue: &QueueHandle<T>, item: T) -> Result<(), FreeRtosError> {
+    queue.send_from_isr(&self.ic, item)
+  }
+
+  /// Increment the semaphore or unlock the mutex.
+  ///
+  /// See [`SemaphoreHandle::give_from_isr`](crate::sync::SemaphoreHandle::give_from_isr).
+  #[cfg(feature = "sync")]
+  #[inline]
+  pub fn semaphore_give(&self, semaphore: &SemaphoreHandle) -> Result<bool, FreeRtosError> {
+    semaphore.give_from_isr(&self.ic)
+  }
+
+  /// Notify a task.
+  ///
+  /// See [`TaskHandle::notify_from_isr`].
+  #[inline]
+  pub fn notify(&self, task: &TaskHandle, notification: TaskNotification) -> Result<bool, FreeRtosError> {
+    task.notify_from_isr(notification, &self.ic)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn track_woken_returns_flag_of_single_call() {
+    let ic = InterruptContext::new();
+
+    let (res, woken) = ic.track_woken(|woken| unsafe { *woken = pdTRUE; 1 });
+    assert_eq!(res, 1);
+    assert!(woken);
+
+    // A later call which does not wake a task reports `false`,
+    // even though the context already needs to yield.
+    let (res, woken) = ic.track_woken(|_| 2);
+    assert_eq!(res, 2);
+    assert!(!woken);
+
+    assert!(ic.into_should_yield());
+  }
+
+  #[test]
+  fn track_woken_does_not_reset_context() {
+    let ic = InterruptContext::new();
+
+    let (_, woken) = ic.track_woken(|woken| unsafe { *woken = pdFALSE });
+    assert!(!woken);
+    assert!(!ic.should_yield());
+
+    let (_, woken) = ic.track_woken(|woken| unsafe { *woken = pdTRUE });
+    assert!(woken);
+
+    let (_, woken) = ic.track_woken(|woken| unsafe { *woken = pdFALSE });
+    assert!(!woken);
+
+    assert!(ic.into_should_yield());
+  }
+}
@@ -35,6 +35,8 @@ extern crate alloc as alloc2;
 
 mod assertion_handler;
 pub use assertion_handler::set_assertion_handler;
+#[cfg(freertos_feature = "dynamic_allocation")]
+pub use assertion_handler::set_boxed_assertion_handler;
 
 mod error;
 mod shim;
@@ -42,6 +44,8 @@ pub mod ffi;
 
 pub mod alloc;
 pub use alloc::Allocator;
+#[cfg(freertos_feature = "heap_free_size")]
+pub use alloc::free_heap_size;
 
 #[cfg(feature = "critical_section")]
 pub mod critical_section;
@@ -53,14 +57,20 @@ pub mod sync;
 #[cfg(feature = "sync")]
 pub use sync::*;
 
+#[cfg(feature = "sync")]
+pub mod patterns;
+
 pub mod task;
 
+#[cfg(feature = "time")]
+pub mod time;
+
 #[cfg(feature = "time")]
 pub mod timer;
 pub use crate::timer::*;
 
 mod hooks;
-pub use hooks::{set_tick_hook, set_idle_hook};
+pub use hooks::{set_tick_hook, set_idle_hook, set_no_tasks_hook};
 
 
 #[cfg(any(feature = "time", feature = "sync"))]
@@ -79,4 +89,4 @@ pub use crate::task::*;
 #[cfg(any(feature = "time", feature = "sync"))]
 pub use crate::ticks::*;
 
-pub use utils::cpu_clock_hz;
+pub use utils::{cpu_clock_hz, tick_period_ms, tick_rate_hz};
@@ -0,0 +1,159 @@
+use core::ops::Deref;
+
+use alloc2::sync::Arc;
+
+use crate::{
+  shim::portMAX_DELAY,
+  sync::Queue,
+  task::{Scheduler, TaskBuilder},
+  FreeRtosError,
+  Ticks,
+};
+
+enum Envelope<Msg, Reply> {
+  Message {
+    msg: Msg,
+    reply: Option<Arc<Queue<Option<Reply>, 1>>>,
+  },
+  Stop,
+}
+
+type Mailbox<Msg, Reply, const SIZE: usize> = Queue<Envelope<Msg, Reply>, SIZE>;
+
+/// A task which owns some state and processes messages sent to it.
+///
+/// Messages are processed one at a time in the order they were received. A
+/// message handler can optionally produce a reply, which is routed back to the
+/// sender if the message was sent using [`ActorRef::ask`].
+///
+/// Dropping the `Actor` waits for space in the mailbox and tells the actor to stop.
+/// The actor processes all messages sent before, then drops its state and its task
+/// returns. Messages sent afterwards using an [`ActorRef`] are not processed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use core::time::Duration;
+///
+/// use freertos_rust::{patterns::Actor, task::Task};
+///
+/// enum Command {
+///   Add(u32),
+///   Get,
+/// }
+///
+/// let counter = Actor::<Command, u32>::spawn(&Task::new().name("counter"), 0, |count, command| {
+///   match command {
+///     Command::Add(n) => { *count += n; None },
+///     Command::Get => Some(*count),
+///   }
+/// });
+///
+/// counter.tell(Command::Add(3), Duration::MAX).unwrap();
+/// assert_eq!(counter.ask(Command::Get, Duration::MAX), Ok(Some(3)));
+/// ```
+pub struct Actor<Msg, Reply, const SIZE: usize = 8> {
+  actor_ref: ActorRef<Msg, Reply, SIZE>,
+}
+
+impl<Msg, Reply, const SIZE: usize> Actor<Msg, Reply, SIZE>
+where
+  Msg: Send + 'static,
+  Reply: Send + 'static,
+{
+  /// Spawn a new actor task owning `state`, which calls `handler` for every received message.
+  pub fn spawn<State, F>(builder: &TaskBuilder<'_>, mut state: State, mut handler: F) -> Self
+  where
+    State: Send + 'static,
+    F: FnMut(&mut State, Msg) -> Option<Reply> + Send + 'static,
+  {
+    let mailbox: Arc<Mailbox<Msg, Reply, SIZE>> = Arc::new(Queue::new());
+
+    {
+      let mailbox = Arc::clone(&mailbox);
+
+      // The task deletes itself once it returns after receiving `Envelope::Stop`.
+      builder.create(move |_| loop {
+        let Ok(envelope) = mailbox.receive(Ticks::new(portMAX_DELAY)) else {
+          continue
+        };
+
+        let Envelope::Message { msg, reply } = envelope else {
+          return
+        };
+
+        let reply_value = handler(&mut state, msg);
+
+        if let Some(reply_queue) = reply {
+          let _ = reply_queue.send(reply_value, Ticks::new(0));
+        }
+      }).detach();
+    }
+
+    Self { actor_ref: ActorRef { mailbox } }
+  }
+
+  /// Get a reference for sending messages to this actor.
+  pub fn actor_ref(&self) -> ActorRef<Msg, Reply, SIZE> {
+    self.actor_ref.clone()
+  }
+}
+
+impl<Msg, Reply, const SIZE: usize> Drop for Actor<Msg, Reply, SIZE> {
+  fn drop(&mut self) {
+    let _ = self.actor_ref.mailbox.send(Envelope::Stop, Ticks::new(portMAX_DELAY));
+  }
+}
+
+impl<Msg, Reply, const SIZE: usize> Deref for Actor<Msg, Reply, SIZE> {
+  type Target = ActorRef<Msg, Reply, SIZE>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.actor_ref
+  }
+}
+
+/// A reference for sending messages to an [`Actor`].
+pub struct ActorRef<Msg, Reply, const SIZE: usize = 8> {
+  mailbox: Arc<Mailbox<Msg, Reply, SIZE>>,
+}
+
+impl<Msg, Reply, const SIZE: usize> Clone for ActorRef<Msg, Reply, SIZE> {
+  fn clone(&self) -> Self {
+    Self { mailbox: Arc::clone(&self.mailbox) }
+  }
+}
+
+impl<Msg, Reply, const SIZE: usize> ActorRef<Msg, Reply, SIZE>
+where
+  Msg: Send + 'static,
+  Reply: Send + 'static,
+{
+  /// Send a message to the actor without waiting for a reply.
+  ///
+  /// Any reply produced by the actor is discarded.
+  pub fn tell(&self, msg: Msg, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
+    self.mailbox.send(Envelope::Message { msg, reply: None }, timeout)
+  }
+
+  /// Send a message to the actor and wait for its reply.
+  ///
+  /// The `timeout` is the total time for sending the message and waiting for the reply.
+  /// Returns `Ok(None)` if the actor did not produce a reply for this message.
+  pub fn ask(&self, msg: Msg, timeout: impl Into<Ticks>) -> Result<Option<Reply>, FreeRtosError> {
+    let timeout = timeout.into();
+    let start = Scheduler::tick_count();
+
+    let reply_queue = Arc::new(Queue::new());
+    self.mailbox.send(Envelope::Message { msg, reply: Some(Arc::clone(&reply_queue)) }, timeout)?;
+
+    let remaining = if timeout.ticks == portMAX_DELAY {
+      timeout
+    } else {
+      let elapsed = Scheduler::tick_count().wrapping_sub(start);
+      Ticks::new(timeout.ticks.saturating_sub(elapsed.ticks))
+    };
+
+    reply_queue.receive(remaining)
+  }
+}
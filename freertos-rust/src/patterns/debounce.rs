@@ -0,0 +1,85 @@
+//! Debouncing of noisy inputs using a software timer.
+
+use core::{
+  sync::atomic::{AtomicBool, Ordering},
+  time::Duration,
+};
+
+use alloc2::sync::Arc;
+
+use crate::{
+  timer::Timer,
+  FreeRtosError,
+  InterruptContext,
+  Ticks,
+};
+
+/// A debouncer for a noisy binary input, e.g. a GPIO pin.
+///
+/// Every call to [`edge`](Debouncer::edge) (or [`edge_from_isr`](Debouncer::edge_from_isr))
+/// records the new input level and (re)starts a one-shot timer. Only once the input has not
+/// changed for a whole debounce period does the timer fire and call the `stable` callback with
+/// the last recorded level. Edges arriving faster than the debounce period keep restarting the
+/// timer, so the callback is not called until the input settles.
+///
+/// The callback runs in the timer daemon task.
+///
+/// # Examples
+///
+/// ```no_run
+/// use core::time::Duration;
+///
+/// use freertos_rust::{patterns::debounce::Debouncer, InterruptContext};
+///
+/// let debouncer = Debouncer::new(Duration::from_millis(20), |level| {
+///   println!("Button is now {}.", if level { "released" } else { "pressed" });
+/// }).unwrap();
+///
+/// // Inside the GPIO interrupt handler:
+/// let ic = InterruptContext::new();
+/// let _ = debouncer.edge_from_isr(false, &ic);
+/// ```
+pub struct Debouncer {
+  timer: Timer<'static>,
+  level: Arc<AtomicBool>,
+}
+
+impl Debouncer {
+  /// Create a new debouncer calling `stable` once the input has been stable for `period`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the timer could not be created.
+  pub fn new<F>(period: impl Into<Duration>, stable: F) -> Result<Self, FreeRtosError>
+  where
+    F: Fn(bool) + Send + 'static,
+  {
+    let level = Arc::new(AtomicBool::new(false));
+
+    let timer = {
+      let level = Arc::clone(&level);
+
+      Timer::new()
+        .period(period)
+        .auto_reload(false)
+        .create(move |_| stable(level.load(Ordering::Acquire)))?
+    };
+
+    Ok(Self { timer, level })
+  }
+
+  /// Record an edge with the new input `level` and restart the debounce period.
+  ///
+  /// The `timeout` is the time to wait for the timer command queue to have space.
+  pub fn edge(&self, level: bool, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
+    self.level.store(level, Ordering::Release);
+    self.timer.start(timeout)
+  }
+
+  /// Record an edge with the new input `level` and restart the debounce period
+  /// from within an interrupt service routine.
+  pub fn edge_from_isr(&self, level: bool, ic: &InterruptContext) -> Result<(), FreeRtosError> {
+    self.level.store(level, Ordering::Release);
+    self.timer.start_from_isr(ic)
+  }
+}
@@ -0,0 +1,9 @@
+//! Common concurrency patterns built on top of FreeRTOS primitives.
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+mod actor;
+#[cfg(freertos_feature = "dynamic_allocation")]
+pub use actor::*;
+
+#[cfg(all(feature = "time", freertos_feature = "dynamic_allocation"))]
+pub mod debounce;
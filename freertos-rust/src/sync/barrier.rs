@@ -0,0 +1,109 @@
+use crate::{
+  shim::portMAX_DELAY,
+  sync::{Counting, Mutex, Semaphore},
+  Ticks,
+};
+
+/// A barrier enabling `N` tasks to synchronize the beginning of some computation.
+///
+/// A barrier can be reused once all tasks have been released. Every generation
+/// passes through two phases, each guarded by its own semaphore:
+///
+/// 1. Arriving tasks increment the counter and wait. The last task to arrive
+///    releases all `N` tasks.
+/// 2. Released tasks decrement the counter and wait again. The last task to
+///    leave releases all `N` tasks from the barrier.
+///
+/// Since no task can leave phase 2 before all tasks have left phase 1, a fast
+/// task re-entering the barrier cannot take a permit intended for a slow task
+/// of the previous generation.
+///
+/// # Examples
+///
+/// ```no_run
+/// extern crate alloc;
+/// use alloc::sync::Arc;
+///
+/// use freertos_rust::{sync::Barrier, task::Task};
+///
+/// let barrier = Arc::new(Barrier::<3>::new());
+///
+/// let tasks = (0..3).map(|_| {
+///   let barrier = Arc::clone(&barrier);
+///   Task::new().create(move |_| {
+///     // ...
+///     barrier.wait();
+///     // ...
+///   })
+/// }).collect::<Vec<_>>();
+/// ```
+pub struct Barrier<const N: usize> {
+  count: Mutex<usize>,
+  arrive: Semaphore<Counting<0, N>>,
+  leave: Semaphore<Counting<0, N>>,
+}
+
+/// Result returned by [`Barrier::wait`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierWaitResult {
+  is_leader: bool,
+}
+
+impl BarrierWaitResult {
+  /// Whether this task was the last one to arrive at the barrier.
+  ///
+  /// Exactly one task per generation is the leader.
+  #[inline]
+  pub fn is_leader(&self) -> bool {
+    self.is_leader
+  }
+}
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+impl<const N: usize> Barrier<N> {
+  /// Create a new barrier for `N` tasks.
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    assert!(N > 0);
+
+    Self {
+      count: Mutex::new(0),
+      arrive: Semaphore::new_counting(),
+      leave: Semaphore::new_counting(),
+    }
+  }
+}
+
+impl<const N: usize> Barrier<N> {
+  /// Block until all `N` tasks have arrived at the barrier.
+  pub fn wait(&self) -> BarrierWaitResult {
+    let is_leader = {
+      let mut count = self.count.lock().unwrap();
+      *count += 1;
+
+      if *count == N {
+        for _ in 0..N {
+          let _ = self.arrive.give();
+        }
+        true
+      } else {
+        false
+      }
+    };
+    let _ = self.arrive.take(Ticks::new(portMAX_DELAY));
+
+    {
+      let mut count = self.count.lock().unwrap();
+      *count -= 1;
+
+      if *count == 0 {
+        for _ in 0..N {
+          let _ = self.leave.give();
+        }
+      }
+    }
+    let _ = self.leave.take(Ticks::new(portMAX_DELAY));
+
+    BarrierWaitResult { is_leader }
+  }
+}
@@ -0,0 +1,148 @@
+use core::fmt;
+
+use alloc2::{boxed::Box, vec::Vec};
+
+use crate::{
+  sync::{Frame, MessageBuffer},
+  FreeRtosError,
+  Ticks,
+};
+
+/// A message type which can be sent over a [`TypedBus`].
+pub trait Message: Frame {
+  /// The tag identifying this message type on the bus.
+  ///
+  /// Each message type registered on a bus must use a unique tag.
+  const TAG: u8;
+}
+
+type Handler = Box<dyn Fn(&[u8]) -> Result<(), FreeRtosError> + Send + Sync>;
+
+/// A message bus carrying different message types over a single [`MessageBuffer`].
+///
+/// Each message is sent as a single frame, consisting of the [`TAG`](Message::TAG) of
+/// its type followed by its encoding, which must fit into `MAX_FRAME - 1` bytes.
+/// When receiving, the frame is decoded and dispatched to the handler registered
+/// for its tag. Like a message buffer, a `TypedBus` supports a single writer and
+/// a single reader.
+///
+/// # Examples
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use freertos_rust::sync::{Frame, Message, TypedBus};
+///
+/// struct Ping(u8);
+///
+/// impl Frame for Ping {
+///   fn encode(&self, buf: &mut [u8]) -> Option<usize> {
+///     *buf.first_mut()? = self.0;
+///     Some(1)
+///   }
+///
+///   fn decode(frame: &[u8]) -> Option<Self> {
+///     match frame {
+///       [n] => Some(Self(*n)),
+///       _ => None,
+///     }
+///   }
+/// }
+///
+/// impl Message for Ping {
+///   const TAG: u8 = 1;
+/// }
+///
+/// let mut bus = TypedBus::<64, 8>::new();
+/// bus.register(|Ping(n): Ping| println!("ping {}", n)).unwrap();
+///
+/// bus.send(&Ping(7), Duration::ZERO).unwrap();
+/// bus.receive(Duration::ZERO).unwrap();
+/// ```
+pub struct TypedBus<const CAPACITY: usize, const MAX_FRAME: usize> {
+  buffer: MessageBuffer<CAPACITY>,
+  handlers: Vec<(u8, Handler)>,
+}
+
+impl<const CAPACITY: usize, const MAX_FRAME: usize> fmt::Debug for TypedBus<CAPACITY, MAX_FRAME> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("TypedBus")
+      .field("buffer", &self.buffer.as_ptr())
+      .field("tags", &self.handlers.iter().map(|(tag, _)| tag).collect::<Vec<_>>())
+      .finish()
+  }
+}
+
+impl<const CAPACITY: usize, const MAX_FRAME: usize> TypedBus<CAPACITY, MAX_FRAME> {
+  /// Create a new dynamic bus without any handlers.
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    assert!(MAX_FRAME > 1);
+
+    Self { buffer: MessageBuffer::new(), handlers: Vec::new() }
+  }
+
+  /// Register the handler for messages of type `T`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if a handler is already registered for [`T::TAG`](Message::TAG).
+  pub fn register<T: Message>(&mut self, handler: impl Fn(T) + Send + Sync + 'static) -> Result<(), FreeRtosError> {
+    if self.handlers.iter().any(|(tag, _)| *tag == T::TAG) {
+      return Err(FreeRtosError::InvalidParameter)
+    }
+
+    self.handlers.push((T::TAG, Box::new(move |frame| {
+      handler(T::decode(frame).ok_or(FreeRtosError::InvalidData)?);
+      Ok(())
+    })));
+
+    Ok(())
+  }
+
+  /// Send a message.
+  ///
+  /// Blocks until there is enough space for the whole frame or the timeout expires.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if the encoded message does not fit into
+  /// `MAX_FRAME - 1` bytes and [`FreeRtosError::QueueFull`] if the frame could not be written.
+  pub fn send<T: Message>(&self, message: &T, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
+    let mut buf = [0; MAX_FRAME];
+    buf[0] = T::TAG;
+
+    let len = match message.encode(&mut buf[1..]) {
+      Some(len) if len < MAX_FRAME => len,
+      _ => return Err(FreeRtosError::InvalidParameter),
+    };
+
+    self.buffer.send(&buf[..(1 + len)], timeout)
+  }
+
+  /// Receive a single message and dispatch it to the handler registered for its type.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Timeout`] if no message was received before the timeout expired,
+  /// [`FreeRtosError::Unavailable`] if no handler is registered for the received message type
+  /// and [`FreeRtosError::InvalidData`] if the message could not be decoded.
+  /// In all of these cases, the received message is discarded.
+  pub fn receive(&self, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
+    let mut buf = [0; MAX_FRAME];
+    let len = self.buffer.receive(&mut buf, timeout)?;
+
+    let (tag, frame) = buf[..len].split_first().ok_or(FreeRtosError::InvalidData)?;
+    let (_, handler) = self.handlers.iter()
+      .find(|(t, _)| t == tag)
+      .ok_or(FreeRtosError::Unavailable)?;
+
+    handler(frame)
+  }
+
+  /// Check whether there are no messages on the bus.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.buffer.is_empty()
+  }
+}
@@ -0,0 +1,146 @@
+use core::{fmt, mem::ManuallyDrop, ptr};
+
+use crate::{
+  sync::{Binary, QueueHandle, QueueSet, Semaphore},
+  FreeRtosError,
+  InterruptContext,
+  Ticks,
+};
+
+/// A token for cancelling a blocking [`QueueHandle::receive_cancellable`].
+///
+/// The token is bound to a single queue. Internally, the queue and a binary
+/// semaphore signalling the cancellation are added to a [`QueueSet`] of `SIZE`,
+/// which must be greater than the length of the queue. Once cancelled, a token
+/// stays cancelled, so all following receives fail immediately.
+///
+/// While the token exists, all items must be received from the queue using
+/// [`receive_cancellable`](QueueHandle::receive_cancellable). Receiving them any
+/// other way leaves stale entries in the queue set, which cause later receives
+/// to return [`FreeRtosError::Timeout`] before the timeout expired.
+///
+/// This requires `configUSE_QUEUE_SETS` and dynamic allocation.
+///
+/// # Examples
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use freertos_rust::{sync::{CancelToken, Queue}, FreeRtosError};
+///
+/// let queue = Queue::<u32, 4>::new();
+/// let token = CancelToken::<_, 5>::new(&*queue).unwrap();
+///
+/// queue.send(1, Duration::ZERO).unwrap();
+/// assert_eq!(queue.receive_cancellable(Duration::MAX, &token), Ok(1));
+///
+/// // E.g. from another task during shutdown.
+/// token.cancel();
+/// assert_eq!(queue.receive_cancellable(Duration::MAX, &token), Err(FreeRtosError::Cancelled));
+/// ```
+pub struct CancelToken<'q, T, const SIZE: usize> {
+  queue: &'q QueueHandle<T>,
+  semaphore: ManuallyDrop<Semaphore<Binary>>,
+  set: ManuallyDrop<QueueSet<SIZE>>,
+}
+
+unsafe impl<T: Send, const SIZE: usize> Send for CancelToken<'_, T, SIZE> {}
+unsafe impl<T: Send, const SIZE: usize> Sync for CancelToken<'_, T, SIZE> {}
+
+impl<T, const SIZE: usize> fmt::Debug for CancelToken<'_, T, SIZE> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("CancelToken")
+      .field("queue", &self.queue.as_ptr())
+      .field("cancelled", &self.is_cancelled())
+      .finish()
+  }
+}
+
+impl<'q, T, const SIZE: usize> CancelToken<'q, T, SIZE> {
+  /// Create a new token for cancelling receives from `queue`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if `SIZE` is not greater than the length
+  /// of the queue, or if the queue is not empty or is already part of a queue set.
+  pub fn new(queue: &'q QueueHandle<T>) -> Result<Self, FreeRtosError> {
+    if queue.len() + queue.spaces_available() >= SIZE {
+      return Err(FreeRtosError::InvalidParameter)
+    }
+
+    let semaphore = Semaphore::new_binary();
+    let set = QueueSet::new();
+    set.add(&*semaphore)?;
+    set.add(queue)?;
+
+    Ok(Self { queue, semaphore: ManuallyDrop::new(semaphore), set: ManuallyDrop::new(set) })
+  }
+
+  /// Cancel all current and future receives using this token.
+  #[inline]
+  pub fn cancel(&self) {
+    // Already cancelled if the semaphore cannot be given.
+    let _ = self.semaphore.give();
+  }
+
+  /// Cancel all current and future receives using this token from within an interrupt service routine.
+  #[inline]
+  pub fn cancel_from_isr(&self, ic: &InterruptContext) {
+    let _ = self.semaphore.give_from_isr(ic);
+  }
+
+  /// Check whether this token was cancelled.
+  #[inline]
+  pub fn is_cancelled(&self) -> bool {
+    self.semaphore.count() != 0
+  }
+}
+
+impl<T, const SIZE: usize> Drop for CancelToken<'_, T, SIZE> {
+  fn drop(&mut self) {
+    // Members can only be removed from the set while they are empty. If a member
+    // cannot be removed, it still refers to the set, so the set has to be leaked.
+    let _ = self.semaphore.take(Ticks::new(0));
+    if self.set.remove(&**self.semaphore).is_err() {
+      return
+    }
+
+    unsafe {
+      ManuallyDrop::drop(&mut self.semaphore);
+
+      if self.set.remove(self.queue).is_ok() {
+        ManuallyDrop::drop(&mut self.set);
+      }
+    }
+  }
+}
+
+impl<T> QueueHandle<T> {
+  /// Wait for an item to be available on the queue until the timeout expires or `token` is cancelled.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Cancelled`] if `token` was cancelled, also if an item is available,
+  /// and [`FreeRtosError::Timeout`] if no item was received before the timeout expired.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `token` was not created for this queue.
+  pub fn receive_cancellable<const SIZE: usize>(
+    &self,
+    timeout: impl Into<Ticks>,
+    token: &CancelToken<'_, T, SIZE>,
+  ) -> Result<T, FreeRtosError> {
+    assert!(ptr::eq(self, token.queue), "`token` was created for a different queue");
+
+    if token.is_cancelled() {
+      return Err(FreeRtosError::Cancelled)
+    }
+
+    match token.set.select(timeout) {
+      Some(member) if member.is(&**token.semaphore) => Err(FreeRtosError::Cancelled),
+      Some(_) => self.receive(Ticks::new(0)),
+      None => Err(FreeRtosError::Timeout),
+    }
+  }
+}
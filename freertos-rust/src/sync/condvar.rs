@@ -0,0 +1,210 @@
+use core::{
+  cell::UnsafeCell,
+  fmt,
+  ptr,
+};
+
+use crate::{
+  ffi::TaskHandle_t,
+  shim::{
+    freertos_rs_enter_critical,
+    freertos_rs_exit_critical,
+    pdFALSE,
+    portMAX_DELAY,
+    ulTaskNotifyTake,
+    xTaskGetCurrentTaskHandle,
+  },
+  sync::MutexGuard,
+  task::{Scheduler, TaskHandle},
+  Ticks,
+};
+
+/// A task waiting on a [`Condvar`].
+///
+/// Waiters live on the stack of the waiting task and are linked into the
+/// list of the condition variable for the duration of [`Condvar::wait`].
+struct Waiter {
+  task: TaskHandle_t,
+  notified: bool,
+  next: *mut Waiter,
+}
+
+/// A condition variable, used together with a [`Mutex`](crate::sync::Mutex)
+/// to block a task until some condition becomes true.
+///
+/// Waiting tasks are woken in the order they started waiting using the notification
+/// of their task, i.e. [`TaskHandle::notify_give`]. Like any condition variable,
+/// a `Condvar` is subject to spurious wakeups, e.g. when a waiting task is notified
+/// by other means, so the condition should always be checked in a loop.
+///
+/// # Examples
+///
+/// ```no_run
+/// extern crate alloc;
+/// use alloc::sync::Arc;
+/// use core::time::Duration;
+///
+/// use freertos_rust::{sync::{Condvar, Mutex}, task::Task};
+///
+/// let pair = Arc::new((Mutex::new(false), Condvar::new()));
+///
+/// let pair2 = Arc::clone(&pair);
+/// let _task = Task::new().create(move |_| {
+///   let (ready, condvar) = &*pair2;
+///   *ready.lock().unwrap() = true;
+///   condvar.notify_one();
+/// });
+///
+/// let (ready, condvar) = &*pair;
+/// let mut ready = ready.lock().unwrap();
+/// while !*ready {
+///   ready = condvar.wait(ready, Duration::MAX).0;
+/// }
+/// ```
+pub struct Condvar {
+  waiters: UnsafeCell<*mut Waiter>,
+}
+
+unsafe impl Send for Condvar {}
+unsafe impl Sync for Condvar {}
+
+impl fmt::Debug for Condvar {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Condvar").finish_non_exhaustive()
+  }
+}
+
+impl Default for Condvar {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Result returned by [`Condvar::wait`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitTimeoutResult {
+  timed_out: bool,
+}
+
+impl WaitTimeoutResult {
+  /// Whether the wait ended because the timeout expired instead of a notification.
+  ///
+  /// This is `false` after a spurious wakeup before the timeout expired.
+  #[inline]
+  pub fn timed_out(&self) -> bool {
+    self.timed_out
+  }
+}
+
+impl Condvar {
+  /// Create a new condition variable without any waiting tasks.
+  pub const fn new() -> Self {
+    Self { waiters: UnsafeCell::new(ptr::null_mut()) }
+  }
+
+  /// Run `f` with exclusive access to the list of waiters.
+  #[inline]
+  fn with_waiters<R>(&self, f: impl FnOnce(&mut *mut Waiter) -> R) -> R {
+    unsafe {
+      freertos_rs_enter_critical();
+      let res = f(&mut *self.waiters.get());
+      freertos_rs_exit_critical();
+      res
+    }
+  }
+
+  /// Atomically unlock the mutex, block until this condition variable is notified
+  /// or the timeout expires and lock the mutex again.
+  ///
+  /// The current task is registered as a waiter before the mutex is unlocked,
+  /// so a notification sent by a task holding the mutex is never missed.
+  ///
+  /// Note that this uses the notification of the current task with index 0
+  /// and may therefore consume a notification not sent by this condition variable.
+  pub fn wait<'m, T: ?Sized>(
+    &self,
+    guard: MutexGuard<'m, T>,
+    timeout: impl Into<Ticks>,
+  ) -> (MutexGuard<'m, T>, WaitTimeoutResult) {
+    let waiter = UnsafeCell::new(Waiter {
+      task: unsafe { xTaskGetCurrentTaskHandle() },
+      notified: false,
+      next: ptr::null_mut(),
+    });
+    let waiter = waiter.get();
+
+    // Append to the list, so tasks are woken in the order they started waiting.
+    self.with_waiters(|mut next| unsafe {
+      while !next.is_null() {
+        next = &mut (**next).next;
+      }
+      *next = waiter;
+    });
+
+    let mutex = guard.handle;
+    drop(guard);
+
+    let timeout = timeout.into();
+    let start = Scheduler::tick_count();
+    let woken = unsafe { ulTaskNotifyTake(pdFALSE, timeout.into()) } != 0;
+
+    let notified = self.with_waiters(|mut next| unsafe {
+      if !(*waiter).notified {
+        while *next != waiter {
+          next = &mut (**next).next;
+        }
+        *next = (*waiter).next;
+      }
+
+      (*waiter).notified
+    });
+
+    // The notification arrived after the timeout expired, consume it
+    // so it does not cause a spurious wakeup of the next wait.
+    if notified && !woken {
+      unsafe { ulTaskNotifyTake(pdFALSE, 0) };
+    }
+
+    let timed_out = !notified
+      && timeout != Ticks::new(portMAX_DELAY)
+      && Scheduler::tick_count().wrapping_sub(start) >= timeout;
+
+    (mutex.lock().unwrap(), WaitTimeoutResult { timed_out })
+  }
+
+  /// Wake up one task waiting on this condition variable.
+  ///
+  /// Returns whether a task was woken.
+  pub fn notify_one(&self) -> bool {
+    self.with_waiters(|waiters| unsafe {
+      let waiter = *waiters;
+      if waiter.is_null() {
+        return false
+      }
+
+      *waiters = (*waiter).next;
+      (*waiter).notified = true;
+      TaskHandle::from_ptr((*waiter).task).notify_give();
+      true
+    })
+  }
+
+  /// Wake up all tasks waiting on this condition variable.
+  ///
+  /// Returns the number of tasks woken.
+  pub fn notify_all(&self) -> usize {
+    self.with_waiters(|waiters| unsafe {
+      let mut count = 0;
+
+      while !waiters.is_null() {
+        let waiter = *waiters;
+        *waiters = (*waiter).next;
+        (*waiter).notified = true;
+        TaskHandle::from_ptr((*waiter).task).notify_give();
+        count += 1;
+      }
+
+      count
+    })
+  }
+}
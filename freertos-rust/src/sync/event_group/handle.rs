@@ -0,0 +1,200 @@
+use core::{fmt, ptr};
+
+use crate::{
+  FreeRtosError,
+  InterruptContext,
+  Ticks,
+  ffi::{EventBits_t, EventGroupHandle_t, Pointee},
+  shim::{
+    pdFALSE,
+    pdTRUE,
+    xEventGroupClearBits,
+    xEventGroupGetBits,
+    xEventGroupSetBits,
+    xEventGroupSync,
+    xEventGroupGetBitsFromISR,
+    xEventGroupWaitBits,
+  },
+};
+#[cfg(freertos_feature = "timer_pend_function_call")]
+use crate::shim::{pdPASS, xEventGroupClearBitsFromISR, xEventGroupSetBitsFromISR};
+
+/// A handle for managing an event group.
+///
+/// See [`EventGroup`](crate::sync::EventGroup) for the preferred owned version.
+///
+/// This type is compatible with a raw FreeRTOS [`EventGroupHandle_t`].
+#[repr(transparent)]
+pub struct EventGroupHandle(Pointee<EventGroupHandle_t>);
+
+impl fmt::Debug for EventGroupHandle {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.as_ptr().fmt(f)
+  }
+}
+
+impl EventGroupHandle {
+  /// Create an `EventGroupHandle` from a raw handle.
+  ///
+  /// # Safety
+  ///
+  /// - `ptr` must point to a valid event group.
+  /// - The event group must not be deleted for the lifetime `'a` of the returned `EventGroupHandle`.
+  #[inline]
+  pub const unsafe fn from_ptr<'a>(ptr: EventGroupHandle_t) -> &'a Self {
+    debug_assert!(!ptr.is_null());
+    &*ptr.cast()
+  }
+
+  /// Get the raw event group handle.
+  #[inline]
+  pub const fn as_ptr(&self) -> EventGroupHandle_t {
+    ptr::addr_of!(self.0).cast_mut()
+  }
+
+  /// Set the given `bits`.
+  ///
+  /// Returns the value of the event group at the time this function returns.
+  #[inline]
+  pub fn set_bits(&self, bits: EventBits_t) -> EventBits_t {
+    unsafe { xEventGroupSetBits(self.as_ptr(), bits) }
+  }
+
+  /// Clear the given `bits`.
+  ///
+  /// Returns the value of the event group before the bits were cleared.
+  #[inline]
+  pub fn clear_bits(&self, bits: EventBits_t) -> EventBits_t {
+    unsafe { xEventGroupClearBits(self.as_ptr(), bits) }
+  }
+
+  /// Get the current value of the event group.
+  #[inline]
+  pub fn get_bits(&self) -> EventBits_t {
+    unsafe { xEventGroupGetBits(self.as_ptr()) }
+  }
+
+  /// Set the given `bits` from within an interrupt service routine.
+  ///
+  /// Setting bits is deferred to the timer daemon task.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::QueueFull`] if the timer command queue is full.
+  #[cfg(freertos_feature = "timer_pend_function_call")]
+  #[inline]
+  pub fn set_bits_from_isr(&self, bits: EventBits_t, ic: &InterruptContext) -> Result<(), FreeRtosError> {
+    match unsafe { xEventGroupSetBitsFromISR(self.as_ptr(), bits, ic.as_ptr()) } {
+      pdPASS => Ok(()),
+      _ => Err(FreeRtosError::QueueFull),
+    }
+  }
+
+  /// Clear the given `bits` from within an interrupt service routine.
+  ///
+  /// Clearing bits is deferred to the timer daemon task.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::QueueFull`] if the timer command queue is full.
+  #[cfg(freertos_feature = "timer_pend_function_call")]
+  #[inline]
+  pub fn clear_bits_from_isr(&self, bits: EventBits_t, _ic: &InterruptContext) -> Result<(), FreeRtosError> {
+    match unsafe { xEventGroupClearBitsFromISR(self.as_ptr(), bits) } {
+      pdPASS => Ok(()),
+      _ => Err(FreeRtosError::QueueFull),
+    }
+  }
+
+  /// Get the current value of the event group from within an interrupt service routine.
+  #[inline]
+  pub fn get_bits_from_isr(&self, _ic: &InterruptContext) -> EventBits_t {
+    unsafe { xEventGroupGetBitsFromISR(self.as_ptr()) }
+  }
+
+  /// Wait for any of the given `bits` to be set, or all of them if `wait_for_all` is `true`.
+  ///
+  /// If `clear_on_exit` is `true`, the given `bits` are cleared once the wait condition is met.
+  ///
+  /// Returns the value of the event group at the time the wait condition was met.
+  pub fn wait_bits(
+    &self,
+    bits: EventBits_t,
+    clear_on_exit: bool,
+    wait_for_all: bool,
+    timeout: impl Into<Ticks>,
+  ) -> Result<EventBits_t, FreeRtosError> {
+    let value = unsafe {
+      xEventGroupWaitBits(
+        self.as_ptr(),
+        bits,
+        if clear_on_exit { pdTRUE } else { pdFALSE },
+        if wait_for_all { pdTRUE } else { pdFALSE },
+        timeout.into().into(),
+      )
+    };
+
+    let satisfied = if wait_for_all {
+      value & bits == bits
+    } else {
+      value & bits != 0
+    };
+
+    if satisfied {
+      Ok(value)
+    } else {
+      Err(FreeRtosError::Timeout)
+    }
+  }
+
+  /// Set `set_bits`, then wait for all of `wait_bits` to be set, atomically.
+  ///
+  /// This allows a number of tasks to rendezvous: Each task sets its own bit and
+  /// waits for the bits of all other tasks. Once all bits in `wait_bits` are set,
+  /// they are cleared automatically and all waiting tasks are released.
+  ///
+  /// Returns the value of the event group before the bits were cleared.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Timeout`] if not all bits were set before the timeout
+  /// expired. In this case, bits set by this call are not cleared.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// extern crate alloc;
+  /// use alloc::sync::Arc;
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::{sync::EventGroup, task::Task};
+  ///
+  /// const ALL_TASKS: u32 = 0b111;
+  ///
+  /// let event_group = Arc::new(EventGroup::new());
+  ///
+  /// let tasks = (0..3).map(|i| {
+  ///   let event_group = Arc::clone(&event_group);
+  ///   Task::new().create(move |_| {
+  ///     // ...
+  ///     let bits = event_group.sync(1 << i, ALL_TASKS, Duration::MAX).unwrap();
+  ///     assert_eq!(bits & ALL_TASKS, ALL_TASKS);
+  ///     // ...
+  ///   })
+  /// }).collect::<Vec<_>>();
+  /// ```
+  pub fn sync(
+    &self,
+    set_bits: EventBits_t,
+    wait_bits: EventBits_t,
+    timeout: impl Into<Ticks>,
+  ) -> Result<EventBits_t, FreeRtosError> {
+    let value = unsafe { xEventGroupSync(self.as_ptr(), set_bits, wait_bits, timeout.into().into()) };
+
+    if value & wait_bits == wait_bits {
+      Ok(value)
+    } else {
+      Err(FreeRtosError::Timeout)
+    }
+  }
+}
@@ -0,0 +1,116 @@
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+use core::ptr;
+
+mod handle;
+pub use handle::EventGroupHandle;
+
+use crate::{
+  ffi::EventGroupHandle_t,
+  shim::{vEventGroupDelete, StaticEventGroup_t},
+};
+#[cfg(freertos_feature = "dynamic_allocation")]
+use crate::shim::xEventGroupCreate;
+#[cfg(freertos_feature = "static_allocation")]
+use crate::shim::xEventGroupCreateStatic;
+
+/// An event group.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use freertos_rust::sync::EventGroup;
+///
+/// let event_group = EventGroup::new();
+/// event_group.set_bits(0b01);
+///
+/// assert_eq!(event_group.wait_bits(0b11, true, false, Duration::MAX), Ok(0b01));
+/// assert_eq!(event_group.get_bits(), 0);
+/// ```
+pub struct EventGroup {
+  handle: EventGroupHandle_t,
+}
+
+unsafe impl Send for EventGroup {}
+unsafe impl Sync for EventGroup {}
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+impl EventGroup {
+  /// Create a new dynamic event group.
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    let ptr = unsafe { xEventGroupCreate() };
+    assert!(!ptr.is_null());
+
+    Self { handle: ptr }
+  }
+}
+
+impl Deref for EventGroup {
+  type Target = EventGroupHandle;
+
+  fn deref(&self) -> &Self::Target {
+    unsafe { EventGroupHandle::from_ptr(self.handle) }
+  }
+}
+
+impl Drop for EventGroup {
+  fn drop(&mut self) {
+    unsafe { vEventGroupDelete(self.handle) }
+  }
+}
+
+/// A statically allocated event group.
+///
+/// # Example
+///
+/// ```
+/// use core::{mem::MaybeUninit, time::Duration};
+///
+/// use freertos_rust::sync::StaticEventGroup;
+///
+/// let event_group = StaticEventGroup::new_static(unsafe {
+///   static mut EVENT_GROUP: MaybeUninit<StaticEventGroup> = MaybeUninit::uninit();
+///   &mut EVENT_GROUP
+/// });
+/// event_group.set_bits(0b10);
+///
+/// assert_eq!(event_group.wait_bits(0b10, true, true, Duration::MAX), Ok(0b10));
+/// ```
+pub struct StaticEventGroup {
+  data: StaticEventGroup_t,
+}
+
+unsafe impl Send for StaticEventGroup {}
+unsafe impl Sync for StaticEventGroup {}
+
+#[cfg(freertos_feature = "static_allocation")]
+impl StaticEventGroup {
+  /// Create a new static event group.
+  pub fn new_static(event_group: &'static mut MaybeUninit<Self>) -> &'static Self {
+    let event_group_ptr = event_group.as_mut_ptr();
+
+    unsafe {
+      let ptr = xEventGroupCreateStatic(ptr::addr_of_mut!((*event_group_ptr).data));
+      debug_assert!(!ptr.is_null());
+      debug_assert_eq!(ptr, ptr::addr_of!((*event_group_ptr).data) as EventGroupHandle_t);
+      event_group.assume_init_ref()
+    }
+  }
+}
+
+impl Deref for StaticEventGroup {
+  type Target = EventGroupHandle;
+
+  fn deref(&self) -> &Self::Target {
+    unsafe { EventGroupHandle::from_ptr(ptr::addr_of!(self.data) as EventGroupHandle_t) }
+  }
+}
+
+impl Drop for StaticEventGroup {
+  fn drop(&mut self) {
+    unsafe { vEventGroupDelete(self.as_ptr()) }
+  }
+}
@@ -0,0 +1,168 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::{
+  sync::MessageBuffer,
+  FreeRtosError,
+  InterruptContext,
+  Ticks,
+};
+
+/// A value which can be sent as a single frame over a [`FramedBuffer`].
+pub trait Frame: Sized {
+  /// Encode this value into `buf`.
+  ///
+  /// Returns the length of the encoded frame, or `None` if `buf` is too small.
+  fn encode(&self, buf: &mut [u8]) -> Option<usize>;
+
+  /// Decode a value from a whole `frame`.
+  ///
+  /// Returns `None` if the frame is not a valid encoding of a value.
+  fn decode(frame: &[u8]) -> Option<Self>;
+}
+
+/// A typed wrapper around a [`MessageBuffer`], where each message is one encoded value.
+///
+/// Values are encoded into and decoded from a buffer of `MAX_FRAME` bytes on the stack,
+/// so only the actual length of each encoded value is stored in the `CAPACITY` bytes
+/// of the message buffer. Like a message buffer, a `FramedBuffer` supports a single
+/// writer and a single reader.
+///
+/// # Examples
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use freertos_rust::sync::{Frame, FramedBuffer};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Command {
+///   Stop,
+///   Move { x: i16, y: i16 },
+/// }
+///
+/// impl Frame for Command {
+///   fn encode(&self, buf: &mut [u8]) -> Option<usize> {
+///     match self {
+///       Self::Stop => {
+///         *buf.first_mut()? = 0;
+///         Some(1)
+///       },
+///       Self::Move { x, y } => {
+///         let buf = buf.get_mut(..5)?;
+///         buf[0] = 1;
+///         buf[1..3].copy_from_slice(&x.to_le_bytes());
+///         buf[3..5].copy_from_slice(&y.to_le_bytes());
+///         Some(5)
+///       },
+///     }
+///   }
+///
+///   fn decode(frame: &[u8]) -> Option<Self> {
+///     match frame {
+///       [0] => Some(Self::Stop),
+///       [1, x0, x1, y0, y1] => Some(Self::Move {
+///         x: i16::from_le_bytes([*x0, *x1]),
+///         y: i16::from_le_bytes([*y0, *y1]),
+///       }),
+///       _ => None,
+///     }
+///   }
+/// }
+///
+/// let commands = FramedBuffer::<Command, 64, 8>::new();
+///
+/// commands.send(&Command::Move { x: 3, y: -4 }, Duration::ZERO).unwrap();
+/// commands.send(&Command::Stop, Duration::ZERO).unwrap();
+///
+/// assert_eq!(commands.receive(Duration::ZERO), Ok(Command::Move { x: 3, y: -4 }));
+/// assert_eq!(commands.receive(Duration::ZERO), Ok(Command::Stop));
+/// ```
+pub struct FramedBuffer<T, const CAPACITY: usize, const MAX_FRAME: usize> {
+  buffer: MessageBuffer<CAPACITY>,
+  _marker: PhantomData<fn(T) -> T>,
+}
+
+impl<T, const CAPACITY: usize, const MAX_FRAME: usize> fmt::Debug for FramedBuffer<T, CAPACITY, MAX_FRAME> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.buffer.as_ptr().fmt(f)
+  }
+}
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+impl<T: Frame, const CAPACITY: usize, const MAX_FRAME: usize> FramedBuffer<T, CAPACITY, MAX_FRAME> {
+  /// Create a new dynamic framed buffer.
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    assert!(MAX_FRAME > 0);
+
+    Self { buffer: MessageBuffer::new(), _marker: PhantomData }
+  }
+}
+
+impl<T: Frame, const CAPACITY: usize, const MAX_FRAME: usize> FramedBuffer<T, CAPACITY, MAX_FRAME> {
+  /// Encode `value` and send it as a single message.
+  ///
+  /// Blocks until there is enough space for the whole frame or the timeout expires.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if the encoded value does not fit into
+  /// `MAX_FRAME` bytes and [`FreeRtosError::QueueFull`] if the frame could not be written.
+  pub fn send(&self, value: &T, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
+    let mut buf = [0; MAX_FRAME];
+    let len = Self::encode(value, &mut buf)?;
+    self.buffer.send(&buf[..len], timeout)
+  }
+
+  /// Encode `value` and send it as a single message from within an interrupt service routine.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if the encoded value does not fit into
+  /// `MAX_FRAME` bytes and [`FreeRtosError::QueueFull`] if there is not enough space for the frame.
+  pub fn send_from_isr(&self, value: &T, ic: &InterruptContext) -> Result<(), FreeRtosError> {
+    let mut buf = [0; MAX_FRAME];
+    let len = Self::encode(value, &mut buf)?;
+    self.buffer.send_from_isr(&buf[..len], ic)
+  }
+
+  /// Receive a single message and decode it.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Timeout`] if no message was received before the timeout expired
+  /// and [`FreeRtosError::InvalidData`] if the message could not be decoded, in which case it is
+  /// discarded. Like [`MessageBufferHandle::receive`](crate::sync::MessageBufferHandle::receive),
+  /// returns [`FreeRtosError::InvalidParameter`] if the next message is longer than `MAX_FRAME`,
+  /// which can only happen if it was sent bypassing the `FramedBuffer`, and leaves it in the buffer.
+  pub fn receive(&self, timeout: impl Into<Ticks>) -> Result<T, FreeRtosError> {
+    let mut buf = [0; MAX_FRAME];
+    let len = self.buffer.receive(&mut buf, timeout)?;
+    T::decode(&buf[..len]).ok_or(FreeRtosError::InvalidData)
+  }
+
+  /// Receive a single message from within an interrupt service routine and decode it.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Unavailable`] if the buffer is empty and otherwise the same errors
+  /// as [`receive`](Self::receive).
+  pub fn receive_from_isr(&self, ic: &InterruptContext) -> Result<T, FreeRtosError> {
+    let mut buf = [0; MAX_FRAME];
+    let len = self.buffer.receive_from_isr(&mut buf, ic)?;
+    T::decode(&buf[..len]).ok_or(FreeRtosError::InvalidData)
+  }
+
+  /// Check whether there are no messages in the buffer.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.buffer.is_empty()
+  }
+
+  fn encode(value: &T, buf: &mut [u8; MAX_FRAME]) -> Result<usize, FreeRtosError> {
+    match value.encode(buf) {
+      Some(len) if len <= MAX_FRAME => Ok(len),
+      _ => Err(FreeRtosError::InvalidParameter),
+    }
+  }
+}
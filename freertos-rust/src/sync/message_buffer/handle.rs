@@ -0,0 +1,147 @@
+use core::{fmt, ptr};
+
+use crate::{
+  InterruptContext,
+  Ticks,
+  ffi::{MessageBufferHandle_t, Pointee},
+  shim::{
+    pdTRUE,
+    xMessageBufferIsEmpty,
+    xMessageBufferIsFull,
+    xMessageBufferNextLengthBytes,
+    xMessageBufferReceive,
+    xMessageBufferReceiveFromISR,
+    xMessageBufferSend,
+    xMessageBufferSendFromISR,
+    xMessageBufferSpacesAvailable,
+  },
+  FreeRtosError,
+};
+
+/// A handle for managing a message buffer.
+///
+/// See [`MessageBuffer`](crate::sync::MessageBuffer) for the preferred owned version.
+///
+/// This type is compatible with a raw FreeRTOS [`MessageBufferHandle_t`].
+#[repr(transparent)]
+pub struct MessageBufferHandle(Pointee<MessageBufferHandle_t>);
+
+impl fmt::Debug for MessageBufferHandle {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.as_ptr().fmt(f)
+  }
+}
+
+impl MessageBufferHandle {
+  /// Create a `MessageBufferHandle` from a raw handle.
+  ///
+  /// # Safety
+  ///
+  /// - `ptr` must point to a valid message buffer.
+  /// - The message buffer must not be deleted for the lifetime `'a` of the returned `MessageBufferHandle`.
+  #[inline]
+  pub const unsafe fn from_ptr<'a>(ptr: MessageBufferHandle_t) -> &'a Self {
+    debug_assert!(!ptr.is_null());
+    &*ptr.cast()
+  }
+
+  /// Get the raw message buffer handle.
+  #[inline]
+  pub const fn as_ptr(&self) -> MessageBufferHandle_t {
+    ptr::addr_of!(self.0).cast_mut()
+  }
+
+  /// Send a message to the message buffer.
+  ///
+  /// Blocks until there is enough space for the whole message or the timeout expires.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::QueueFull`] if the message could not be written.
+  #[inline]
+  pub fn send(&self, message: &[u8], timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
+    match unsafe { xMessageBufferSend(self.as_ptr(), message.as_ptr().cast(), message.len(), timeout.into().into()) } {
+      0 => Err(FreeRtosError::QueueFull),
+      _ => Ok(()),
+    }
+  }
+
+  /// Send a message to the message buffer from within an interrupt service routine.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::QueueFull`] if there is not enough space for the message.
+  #[inline]
+  pub fn send_from_isr(&self, message: &[u8], ic: &InterruptContext) -> Result<(), FreeRtosError> {
+    match unsafe { xMessageBufferSendFromISR(self.as_ptr(), message.as_ptr().cast(), message.len(), ic.as_ptr()) } {
+      0 => Err(FreeRtosError::QueueFull),
+      _ => Ok(()),
+    }
+  }
+
+  /// Receive a message from the message buffer.
+  ///
+  /// Returns the length of the received message.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if the next message does not fit into `buf`,
+  /// in which case the message is left in the message buffer, and [`FreeRtosError::Timeout`]
+  /// if no message was received before the timeout expired.
+  #[inline]
+  pub fn receive(&self, buf: &mut [u8], timeout: impl Into<Ticks>) -> Result<usize, FreeRtosError> {
+    match unsafe { xMessageBufferReceive(self.as_ptr(), buf.as_mut_ptr().cast(), buf.len(), timeout.into().into()) } {
+      0 => Err(self.receive_error(buf, FreeRtosError::Timeout)),
+      len => Ok(len),
+    }
+  }
+
+  /// Receive a message from the message buffer from within an interrupt service routine.
+  ///
+  /// Returns the length of the received message.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if the next message does not fit into `buf`,
+  /// in which case the message is left in the message buffer, and [`FreeRtosError::Unavailable`]
+  /// if the message buffer is empty.
+  #[inline]
+  pub fn receive_from_isr(&self, buf: &mut [u8], ic: &InterruptContext) -> Result<usize, FreeRtosError> {
+    match unsafe { xMessageBufferReceiveFromISR(self.as_ptr(), buf.as_mut_ptr().cast(), buf.len(), ic.as_ptr()) } {
+      0 => Err(self.receive_error(buf, FreeRtosError::Unavailable)),
+      len => Ok(len),
+    }
+  }
+
+  fn receive_error(&self, buf: &[u8], empty_error: FreeRtosError) -> FreeRtosError {
+    if unsafe { xMessageBufferNextLengthBytes(self.as_ptr()) } > buf.len() {
+      FreeRtosError::InvalidParameter
+    } else {
+      empty_error
+    }
+  }
+
+  /// Check whether the message buffer is empty.
+  ///
+  /// This can also be called from within an interrupt service routine.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    unsafe { xMessageBufferIsEmpty(self.as_ptr()) == pdTRUE }
+  }
+
+  /// Check whether the message buffer is full.
+  ///
+  /// This can also be called from within an interrupt service routine.
+  #[inline]
+  pub fn is_full(&self) -> bool {
+    unsafe { xMessageBufferIsFull(self.as_ptr()) == pdTRUE }
+  }
+
+  /// Get the number of free bytes in the message buffer.
+  ///
+  /// Note that each message additionally requires space for storing its length.
+  #[inline]
+  pub fn spaces_available(&self) -> usize {
+    unsafe { xMessageBufferSpacesAvailable(self.as_ptr()) }
+  }
+}
@@ -0,0 +1,67 @@
+use core::ops::Deref;
+
+mod handle;
+pub use handle::MessageBufferHandle;
+
+use crate::{
+  ffi::MessageBufferHandle_t,
+  shim::vMessageBufferDelete,
+};
+#[cfg(freertos_feature = "dynamic_allocation")]
+use crate::shim::xMessageBufferCreate;
+
+/// A message buffer for sending variable-length messages from a single writer to a single reader.
+///
+/// Unlike a [`StreamBuffer`](crate::sync::StreamBuffer), message boundaries are preserved.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use freertos_rust::sync::MessageBuffer;
+///
+/// let message_buffer = MessageBuffer::<64>::new();
+/// message_buffer.send(b"hello", Duration::MAX).unwrap();
+/// message_buffer.send(b"world!", Duration::MAX).unwrap();
+///
+/// let mut buf = [0; 16];
+/// let len = message_buffer.receive(&mut buf, Duration::ZERO).unwrap();
+/// assert_eq!(&buf[..len], b"hello");
+/// let len = message_buffer.receive(&mut buf, Duration::ZERO).unwrap();
+/// assert_eq!(&buf[..len], b"world!");
+/// ```
+pub struct MessageBuffer<const CAPACITY: usize> {
+  handle: MessageBufferHandle_t,
+}
+
+unsafe impl<const CAPACITY: usize> Send for MessageBuffer<CAPACITY> {}
+unsafe impl<const CAPACITY: usize> Sync for MessageBuffer<CAPACITY> {}
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+impl<const CAPACITY: usize> MessageBuffer<CAPACITY> {
+  /// Create a new dynamic message buffer.
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    assert!(CAPACITY > 0);
+
+    let ptr = unsafe { xMessageBufferCreate(CAPACITY) };
+    assert!(!ptr.is_null());
+
+    Self { handle: ptr }
+  }
+}
+
+impl<const CAPACITY: usize> Deref for MessageBuffer<CAPACITY> {
+  type Target = MessageBufferHandle;
+
+  fn deref(&self) -> &Self::Target {
+    unsafe { MessageBufferHandle::from_ptr(self.handle) }
+  }
+}
+
+impl<const CAPACITY: usize> Drop for MessageBuffer<CAPACITY> {
+  fn drop(&mut self) {
+    unsafe { vMessageBufferDelete(self.handle) }
+  }
+}
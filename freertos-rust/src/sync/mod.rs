@@ -68,10 +68,37 @@
 //!
 //! ```
 
-
+mod barrier;
+pub use barrier::*;
+#[cfg(freertos_feature = "dynamic_allocation")]
+mod bus;
+#[cfg(freertos_feature = "dynamic_allocation")]
+pub use bus::*;
+#[cfg(all(freertos_feature = "queue_sets", freertos_feature = "dynamic_allocation"))]
+mod cancel;
+#[cfg(all(freertos_feature = "queue_sets", freertos_feature = "dynamic_allocation"))]
+pub use cancel::*;
+mod condvar;
+pub use condvar::*;
+mod event_group;
+pub use event_group::*;
+mod framed;
+pub use framed::*;
+mod message_buffer;
+pub use message_buffer::*;
 mod mutex;
 pub use mutex::*;
+mod notifier;
+pub use notifier::*;
 mod queue;
 pub use queue::*;
+#[cfg(freertos_feature = "queue_sets")]
+mod queue_set;
+#[cfg(freertos_feature = "queue_sets")]
+pub use queue_set::*;
 mod semaphore;
 pub use semaphore::*;
+#[cfg(freertos_feature = "static_allocation")]
+pub mod spsc;
+mod stream_buffer;
+pub use stream_buffer::*;
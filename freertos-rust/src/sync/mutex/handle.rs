@@ -1,11 +1,13 @@
 use core::fmt;
 use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::{
-  shim::portMAX_DELAY,
+  shim::{portMAX_DELAY, xSemaphoreGetMutexHolder, xSemaphoreGetMutexHolderFromISR},
   FreeRtosError,
   sync::SemaphoreHandle,
   ffi::SemaphoreHandle_t,
+  task::{Scheduler, TaskHandle},
   Ticks,
   InterruptContext,
 };
@@ -16,6 +18,9 @@ use super::{
   RecursiveMutexGuard,
 };
 
+/// Flag set in the lock depth while the data is mutably borrowed by [`RecursiveMutexHandle::with`].
+const MUTABLY_BORROWED: usize = 1 << (usize::BITS - 1);
+
 macro_rules! impl_mutex_handle {
   (
     $mutex:ident,
@@ -25,6 +30,7 @@ macro_rules! impl_mutex_handle {
     $(($take_from_isr:ident),)?
     $give:ident $(,)?
     $(($give_from_isr:ident),)?
+    [$($(#[$field_attr:meta])* $field:ident: $field_ty:ty = $field_init:expr),* $(,)?] $(,)?
   ) => {
     /// A handle for managing a mutex.
     ///
@@ -33,6 +39,10 @@ macro_rules! impl_mutex_handle {
     #[doc = concat!("`", stringify!($handle), "<()>` is compatible with a raw FreeRTOS [`SemaphoreHandle_t`].")]
     pub struct $handle<T: ?Sized = ()> {
       pub(super) ptr: SemaphoreHandle_t,
+      $(
+        $(#[$field_attr])*
+        pub(super) $field: $field_ty,
+      )*
       pub(super) data: UnsafeCell<T>,
     }
 
@@ -53,9 +63,18 @@ macro_rules! impl_mutex_handle {
       #[inline]
       pub const unsafe fn from_ptr(ptr: SemaphoreHandle_t) -> Self {
         debug_assert!(!ptr.is_null());
+        Self::from_raw_parts(ptr, ())
+      }
+    }
+
+    impl<T> $handle<T> {
+      /// Create a handle for the mutex `ptr` protecting `data`.
+      #[inline]
+      pub(super) const fn from_raw_parts(ptr: SemaphoreHandle_t, data: T) -> Self {
         Self {
           ptr,
-          data: UnsafeCell::new(()),
+          $($field: $field_init,)*
+          data: UnsafeCell::new(data),
         }
       }
     }
@@ -95,11 +114,13 @@ macro_rules! impl_mutex_handle {
 
       #[inline]
       fn take(&self, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
-        self.handle().$take(timeout)
+        self.handle().$take(timeout)?;
+        self.after_take()
       }
 
       #[inline]
       pub(super) fn give(&self) -> Result<(), FreeRtosError> {
+        self.before_give();
         self.handle().$give()
       }
 
@@ -107,7 +128,7 @@ macro_rules! impl_mutex_handle {
         /// Unlock the mutex from within an interrupt service routine.
         #[inline]
         pub(super) fn give_from_isr(&self, ic: &InterruptContext) -> Result<(), FreeRtosError> {
-          self.handle().$give_from_isr(ic)
+          self.handle().$give_from_isr(ic).map(drop)
         }
       )*
 
@@ -120,6 +141,21 @@ macro_rules! impl_mutex_handle {
         }
       )*
 
+      /// Get the task currently holding the mutex, or `None` if the mutex is not locked.
+      #[inline]
+      pub fn holder(&self) -> Option<&TaskHandle> {
+        let ptr = unsafe { xSemaphoreGetMutexHolder(self.as_ptr()) };
+        (!ptr.is_null()).then(|| unsafe { TaskHandle::from_ptr(ptr) })
+      }
+
+      /// Get the task currently holding the mutex from within an interrupt service routine,
+      /// or `None` if the mutex is not locked.
+      #[inline]
+      pub fn holder_from_isr(&self, _ic: &InterruptContext) -> Option<&TaskHandle> {
+        let ptr = unsafe { xSemaphoreGetMutexHolderFromISR(self.as_ptr()) };
+        (!ptr.is_null()).then(|| unsafe { TaskHandle::from_ptr(ptr) })
+      }
+
       /// Lock the mutex.
       #[inline]
       pub fn lock(&self) -> Result<$guard<'_, T>, FreeRtosError> {
@@ -137,6 +173,16 @@ macro_rules! impl_mutex_handle {
         self.take(timeout)?;
         Ok($guard { handle: self })
       }
+
+      /// Try locking the mutex until the given `timeout` and measure how long it took.
+      ///
+      /// Returns the guard together with the number of ticks spent waiting for the lock,
+      /// which can be accumulated to find contended mutexes.
+      pub fn timed_lock_measured(&self, timeout: impl Into<Ticks>) -> Result<($guard<'_, T>, Ticks), FreeRtosError> {
+        let start = Scheduler::tick_count();
+        let guard = self.timed_lock(timeout)?;
+        Ok((guard, Scheduler::tick_count().wrapping_sub(start)))
+      }
     }
   };
 }
@@ -149,6 +195,7 @@ impl_mutex_handle!(
   (take_from_isr),
   give,
   (give_from_isr),
+  [],
 );
 impl_mutex_handle!(
   RecursiveMutex,
@@ -156,4 +203,88 @@ impl_mutex_handle!(
   RecursiveMutexGuard,
   take_recursive,
   give_recursive,
+  [
+    /// How many times the mutex is currently locked by its holder.
+    depth: AtomicUsize = AtomicUsize::new(0),
+  ],
 );
+
+impl<T: ?Sized> MutexHandle<T> {
+  #[inline(always)]
+  fn after_take(&self) -> Result<(), FreeRtosError> {
+    Ok(())
+  }
+
+  #[inline(always)]
+  fn before_give(&self) {}
+}
+
+impl<T: ?Sized> RecursiveMutexHandle<T> {
+  // NOTE: The depth is only modified by the task holding the mutex,
+  //       so relaxed ordering is sufficient.
+  #[inline]
+  fn after_take(&self) -> Result<(), FreeRtosError> {
+    if self.depth.fetch_add(1, Ordering::Relaxed) & MUTABLY_BORROWED != 0 {
+      self.give()?;
+      return Err(FreeRtosError::Unavailable)
+    }
+
+    Ok(())
+  }
+
+  #[inline]
+  fn before_give(&self) {
+    self.depth.fetch_sub(1, Ordering::Relaxed);
+  }
+
+  /// Lock the mutex until the given `timeout` and call `f` with a mutable reference to the data.
+  ///
+  /// Mutable access is only granted if the mutex is not already locked by the current task.
+  /// While `f` runs, locking the mutex again from within `f` fails.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Timeout`] if the mutex could not be locked before the timeout expired
+  /// and [`FreeRtosError::Unavailable`] if the mutex is already locked by the current task.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::{sync::RecursiveMutex, FreeRtosError};
+  ///
+  /// let mutex = RecursiveMutex::new(0u32);
+  ///
+  /// mutex.with(Duration::MAX, |value| *value += 1).unwrap();
+  ///
+  /// let guard = mutex.lock().unwrap();
+  /// assert_eq!(mutex.with(Duration::MAX, |value| *value += 1), Err(FreeRtosError::Unavailable));
+  /// drop(guard);
+  ///
+  /// assert_eq!(*mutex.lock().unwrap(), 1);
+  /// ```
+  pub fn with<R>(&self, timeout: impl Into<Ticks>, f: impl FnOnce(&mut T) -> R) -> Result<R, FreeRtosError> {
+    struct Borrow<'m, T: ?Sized>(&'m RecursiveMutexHandle<T>);
+
+    impl<T: ?Sized> Drop for Borrow<'_, T> {
+      fn drop(&mut self) {
+        self.0.depth.fetch_and(!MUTABLY_BORROWED, Ordering::Relaxed);
+        let _ = self.0.give();
+      }
+    }
+
+    self.take(timeout)?;
+
+    if self.depth.load(Ordering::Relaxed) != 1 {
+      self.give()?;
+      return Err(FreeRtosError::Unavailable)
+    }
+
+    self.depth.fetch_or(MUTABLY_BORROWED, Ordering::Relaxed);
+    let _borrow = Borrow(self);
+
+    // SAFETY: The mutex is locked exactly once and cannot be locked again while borrowed.
+    Ok(f(unsafe { self.data_mut() }))
+  }
+}
@@ -0,0 +1,163 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::sync::SemaphoreHandle;
+
+use super::{MutexGuard, RecursiveMutexGuard};
+
+/// An RAII mutex guard returned by [`MutexGuard::map`], which can point to a
+/// subfield of the protected data.
+///
+/// When this structure is dropped (falls out of scope), the lock will be unlocked.
+#[must_use = "if unused the `Mutex` will unlock immediately"]
+#[clippy::has_significant_drop]
+pub struct MappedMutexGuard<'m, U: ?Sized> {
+  handle: &'m SemaphoreHandle,
+  data: *mut U,
+  _marker: PhantomData<&'m mut U>,
+}
+
+unsafe impl<U: ?Sized + Send> Send for MappedMutexGuard<'_, U> {}
+unsafe impl<U: ?Sized + Sync> Sync for MappedMutexGuard<'_, U> {}
+
+impl<'m, T: ?Sized> MutexGuard<'m, T> {
+  /// Make a new [`MappedMutexGuard`] for a component of the locked data.
+  ///
+  /// The mutex stays locked until the returned guard is dropped.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use freertos_rust::sync::{Mutex, MutexGuard};
+  ///
+  /// struct State {
+  ///   count: u32,
+  ///   name: &'static str,
+  /// }
+  ///
+  /// let mutex = Mutex::new(State { count: 0, name: "state" });
+  ///
+  /// {
+  ///   let mut count = MutexGuard::map(mutex.lock().unwrap(), |state| &mut state.count);
+  ///   *count += 1;
+  ///   assert!(mutex.try_lock().is_err());
+  /// }
+  ///
+  /// assert_eq!(mutex.try_lock().unwrap().count, 1);
+  /// ```
+  pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedMutexGuard<'m, U>
+  where
+    F: FnOnce(&mut T) -> &mut U,
+  {
+    let handle = this.handle;
+    // SAFETY: Mutex is locked.
+    let data: *mut U = f(unsafe { handle.data_mut() });
+    let _ = ManuallyDrop::new(this);
+
+    MappedMutexGuard {
+      handle: unsafe { SemaphoreHandle::from_ptr(handle.as_ptr()) },
+      data,
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<U: ?Sized> Deref for MappedMutexGuard<'_, U> {
+  type Target = U;
+
+  /// Dereferences the mapped value.
+  #[inline]
+  fn deref(&self) -> &U {
+    // SAFETY: Mutex is locked.
+    unsafe { &*self.data }
+  }
+}
+
+impl<U: ?Sized> DerefMut for MappedMutexGuard<'_, U> {
+  /// Mutably dereferences the mapped value.
+  #[inline]
+  fn deref_mut(&mut self) -> &mut U {
+    // SAFETY: Mutex is locked.
+    unsafe { &mut *self.data }
+  }
+}
+
+impl<U: ?Sized + fmt::Debug> fmt::Debug for MappedMutexGuard<'_, U> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    (**self).fmt(f)
+  }
+}
+
+impl<U: ?Sized> Drop for MappedMutexGuard<'_, U> {
+  /// Unlocks the mutex.
+  #[inline]
+  fn drop(&mut self) {
+    let _ = self.handle.give();
+  }
+}
+
+/// An RAII recursive mutex guard returned by [`RecursiveMutexGuard::map`], which
+/// can point to a subfield of the protected data.
+///
+/// When this structure is dropped (falls out of scope), the lock will be unlocked.
+#[must_use = "if unused the `RecursiveMutex` will unlock immediately"]
+#[clippy::has_significant_drop]
+pub struct MappedRecursiveMutexGuard<'m, U: ?Sized> {
+  handle: &'m SemaphoreHandle,
+  depth: &'m AtomicUsize,
+  data: *const U,
+  _marker: PhantomData<&'m U>,
+}
+
+unsafe impl<U: ?Sized + Sync> Sync for MappedRecursiveMutexGuard<'_, U> {}
+
+impl<'m, T: ?Sized> RecursiveMutexGuard<'m, T> {
+  /// Make a new [`MappedRecursiveMutexGuard`] for a component of the locked data.
+  ///
+  /// The mutex stays locked until the returned guard is dropped.
+  pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedRecursiveMutexGuard<'m, U>
+  where
+    F: FnOnce(&T) -> &U,
+  {
+    let handle = this.handle;
+    // SAFETY: Mutex is locked.
+    let data: *const U = f(unsafe { handle.data() });
+    let _ = ManuallyDrop::new(this);
+
+    MappedRecursiveMutexGuard {
+      handle: unsafe { SemaphoreHandle::from_ptr(handle.as_ptr()) },
+      depth: &handle.depth,
+      data,
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<U: ?Sized> Deref for MappedRecursiveMutexGuard<'_, U> {
+  type Target = U;
+
+  /// Dereferences the mapped value.
+  #[inline]
+  fn deref(&self) -> &U {
+    // SAFETY: Mutex is locked.
+    unsafe { &*self.data }
+  }
+}
+
+impl<U: ?Sized + fmt::Debug> fmt::Debug for MappedRecursiveMutexGuard<'_, U> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    (**self).fmt(f)
+  }
+}
+
+impl<U: ?Sized> Drop for MappedRecursiveMutexGuard<'_, U> {
+  /// Unlocks the mutex.
+  #[inline]
+  fn drop(&mut self) {
+    self.depth.fetch_sub(1, Ordering::Relaxed);
+    let _ = self.handle.give_recursive();
+  }
+}
@@ -1,4 +1,4 @@
-use core::cell::UnsafeCell;
+use core::ffi::CStr;
 use core::fmt;
 use core::ptr;
 use core::mem::{MaybeUninit, ManuallyDrop};
@@ -10,6 +10,8 @@ use crate::InterruptContext;
 
 mod handle;
 pub use handle::{MutexHandle, RecursiveMutexHandle};
+mod mapped;
+pub use mapped::{MappedMutexGuard, MappedRecursiveMutexGuard};
 
 macro_rules! guard_impl_deref_mut {
   (MutexGuard) => {
@@ -54,13 +56,19 @@ macro_rules! impl_mutex {
           let ptr = $create();
           assert!(!ptr.is_null());
           Self {
-            handle: ManuallyDrop::new($handle {
-              ptr,
-              data: UnsafeCell::new(data),
-            })
+            handle: ManuallyDrop::new($handle::from_raw_parts(ptr, data))
           }
         }
       }
+
+      #[doc = concat!("Create a new dynamic `", stringify!($mutex), "` with the given inner value and add it to the registry with the given name.")]
+      ///
+      /// The mutex is removed from the registry when it is dropped.
+      pub fn new_named(data: T, name: &'static CStr) -> Self {
+        let mutex = Self::new(data);
+        unsafe { vQueueAddToRegistry(mutex.as_ptr(), name.as_ptr()) };
+        mutex
+      }
     }
 
     #[cfg(freertos_feature = "static_allocation")]
@@ -88,10 +96,7 @@ macro_rules! impl_mutex {
           debug_assert_eq!(ptr, ptr::addr_of_mut!((*mutex_ptr).data) as SemaphoreHandle_t);
 
           Self {
-            handle: ManuallyDrop::new($handle {
-              ptr,
-              data: UnsafeCell::new(data),
-            })
+            handle: ManuallyDrop::new($handle::from_raw_parts(ptr, data))
           }
         }
       }
@@ -152,7 +157,7 @@ macro_rules! impl_mutex {
     //                       and cause Futures to not implement `Send`"]
     #[clippy::has_significant_drop]
     pub struct $guard<'m, T: ?Sized> {
-      handle: &'m $handle<T>,
+      pub(crate) handle: &'m $handle<T>,
     }
 
     unsafe impl<T: ?Sized + Sync> Sync for $guard<'_, T> {}
@@ -254,7 +259,7 @@ impl_mutex!(
   /// A mutual exclusion primitive useful for protecting shared data which can be locked recursively.
   ///
   /// [`RecursiveMutexGuard`] does not give mutable references to the contained data,
-  /// use a [`RefCell`](core::cell::RefCell) if you need this.
+  /// use [`with`](RecursiveMutexHandle::with) or a [`RefCell`](core::cell::RefCell) if you need this.
   RecursiveMutex,
   RecursiveMutexHandle,
   RecursiveMutexGuard,
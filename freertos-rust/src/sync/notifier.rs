@@ -0,0 +1,104 @@
+use crate::{
+  shim::configTASK_NOTIFICATION_ARRAY_ENTRIES,
+  task::{CurrentTask, TaskHandle, TaskNotification},
+  FreeRtosError,
+  InterruptContext,
+  Ticks,
+};
+
+/// Receiving side of a single-slot channel based on a task notification index.
+///
+/// A `NotifyReceiver` is bound to the current task and a single notification index,
+/// so all [`Notifier`]s created from it notify the same index, which is the only one
+/// the receiver waits on.
+///
+/// # Examples
+///
+/// ```no_run
+/// use core::time::Duration;
+///
+/// use freertos_rust::{sync::NotifyReceiver, task::Task};
+///
+/// let _task = Task::new().create(|task| {
+///   let mut receiver = NotifyReceiver::new(task, 1);
+///   // SAFETY: This task never returns, so it is never deleted.
+///   let notifier = unsafe { receiver.notifier() };
+///
+///   let _sender = Task::new().create(move |_| {
+///     notifier.send(42).unwrap();
+///   });
+///
+///   assert_eq!(receiver.recv(Duration::MAX), Ok(42));
+///
+///   loop {
+///     task.delay(Duration::MAX);
+///   }
+/// });
+/// ```
+pub struct NotifyReceiver<'t> {
+  task: &'t mut CurrentTask,
+  index: usize,
+}
+
+impl<'t> NotifyReceiver<'t> {
+  /// Create a receiver for the notification `index` of the current task.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `index` is not within \[0, `configTASK_NOTIFICATION_ARRAY_ENTRIES`\).
+  pub fn new(task: &'t mut CurrentTask, index: usize) -> Self {
+    assert!(index < configTASK_NOTIFICATION_ARRAY_ENTRIES as _);
+
+    Self { task, index }
+  }
+
+  /// Create a sender for this receiver.
+  ///
+  /// # Safety
+  ///
+  /// The returned [`Notifier`] is not bound to the lifetime of the receiving task.
+  /// The caller must ensure that the receiving task is not deleted, i.e. does not
+  /// return, while the `Notifier` or any of its copies can still be used.
+  pub unsafe fn notifier(&self) -> Notifier {
+    let task = TaskHandle::from_ptr(self.task.handle().as_ptr());
+    Notifier { task, index: self.index }
+  }
+
+  /// Wait for a value to be sent.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Timeout`] if no value was sent before the timeout expired.
+  pub fn recv(&mut self, timeout: impl Into<Ticks>) -> Result<u32, FreeRtosError> {
+    self.task.wait_for_notification_indexed(self.index, 0, !0, timeout)
+  }
+}
+
+/// Sending side of a [`NotifyReceiver`].
+#[derive(Debug, Clone, Copy)]
+pub struct Notifier {
+  task: &'static TaskHandle,
+  index: usize,
+}
+
+impl Notifier {
+  /// Send a value to the receiver.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::QueueFull`] if the receiver did not receive the previous value yet.
+  pub fn send(&self, value: u32) -> Result<(), FreeRtosError> {
+    self.task.notify_indexed(self.index, TaskNotification::SetValue(value))
+  }
+
+  /// Send a value to the receiver from an interrupt service routine.
+  ///
+  /// Returns whether sending the value woke a task with a higher priority than the interrupted task.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::QueueFull`] if the receiver did not receive the previous value yet.
+  pub fn send_from_isr(&self, value: u32, ic: &InterruptContext) -> Result<bool, FreeRtosError> {
+    self.task.notify_indexed_from_isr(self.index, TaskNotification::SetValue(value), ic)
+  }
+}
@@ -1,24 +1,54 @@
 use core::fmt;
 use core::ffi::CStr;
 use core::marker::PhantomData;
-use core::mem::MaybeUninit;
+use core::mem::{self, MaybeUninit};
+#[cfg(freertos_feature = "static_allocation")]
+use core::mem::{align_of, size_of};
 use core::ptr;
 
 use crate::FreeRtosError;
 use crate::InterruptContext;
 use crate::ffi::Pointee;
 use crate::ffi::QueueHandle_t;
+#[cfg(freertos_feature = "static_allocation")]
+use crate::{
+  ffi::UBaseType_t,
+  shim::{xQueueCreateStatic, StaticQueue_t},
+};
 use crate::shim::{
   pdTRUE,
   errQUEUE_FULL,
   vQueueAddToRegistry,
+  vQueueUnregisterQueue,
   xQueueSend,
   xQueueSendFromISR,
   xQueueReceive,
+  xQueuePeek,
+  xQueuePeekFromISR,
   uxQueueMessagesWaiting,
+  uxQueueMessagesWaitingFromISR,
+  uxQueueSpacesAvailable,
+  xQueueIsQueueEmptyFromISR,
+  xQueueIsQueueFullFromISR,
+  pdFALSE,
+  freertos_rs_enter_critical,
+  freertos_rs_exit_critical,
+  freertos_rs_enter_critical_from_isr,
+  freertos_rs_exit_critical_from_isr,
+  pdPASS,
+  xQueueOverwrite,
+  xQueueOverwriteFromISR,
+  xQueueReceiveFromISR,
+  xQueueReset,
+  xQueueSendToFront,
+  xQueueSendToFrontFromISR,
 };
 use crate::Ticks;
 
+use super::{Drain, IterBlocking};
+#[cfg(feature = "diagnostics")]
+use super::occupancy;
+
 /// A handle for managing a queue.
 ///
 /// See [`Queue`](crate::sync::Queue) for the preferred owned version.
@@ -61,23 +91,180 @@ impl<T> QueueHandle<T> {
     unsafe { vQueueAddToRegistry(self.as_ptr(), name.as_ptr()) }
   }
 
+  /// Remove the queue from the registry.
+  ///
+  /// Deleting a queue automatically removes it from the registry.
+  #[inline]
+  pub fn remove_from_registry(&self) {
+    unsafe { vQueueUnregisterQueue(self.as_ptr()) }
+  }
+
+  /// Drop all items remaining in the queue.
+  ///
+  /// # Safety
+  ///
+  /// The queue must not be used by any other task or interrupt concurrently.
+  pub(super) unsafe fn drop_items(&self) {
+    if !mem::needs_drop::<T>() {
+      return
+    }
+
+    let mut item = MaybeUninit::<T>::uninit();
+    while xQueueReceive(self.as_ptr(), item.as_mut_ptr().cast(), 0) == pdTRUE {
+      item.assume_init_drop();
+    }
+  }
+
   /// Get the number of messages in the queue.
   #[inline]
-  #[allow(clippy::len_without_is_empty)]
   pub fn len(&self) -> usize {
     unsafe { uxQueueMessagesWaiting(self.as_ptr()) as usize }
   }
+
+  /// Get the number of free spaces in the queue.
+  #[inline]
+  pub fn spaces_available(&self) -> usize {
+    unsafe { uxQueueSpacesAvailable(self.as_ptr()) as usize }
+  }
+
+  /// Check whether the queue is empty.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Check whether the queue is full.
+  #[inline]
+  pub fn is_full(&self) -> bool {
+    self.spaces_available() == 0
+  }
+
+  /// Get the number of messages in the queue, from an interrupt.
+  #[inline]
+  pub fn len_from_isr(&self, _ic: &InterruptContext) -> usize {
+    unsafe { uxQueueMessagesWaitingFromISR(self.as_ptr()) as usize }
+  }
+
+  /// Check whether the queue is empty, from an interrupt.
+  #[inline]
+  pub fn is_empty_from_isr(&self, _ic: &InterruptContext) -> bool {
+    unsafe { xQueueIsQueueEmptyFromISR(self.as_ptr()) != pdFALSE }
+  }
+
+  /// Check whether the queue is full, from an interrupt.
+  #[inline]
+  pub fn is_full_from_isr(&self, _ic: &InterruptContext) -> bool {
+    unsafe { xQueueIsQueueFullFromISR(self.as_ptr()) != pdFALSE }
+  }
+
+  /// Get the maximum number of items which were ever in the queue at the same time.
+  ///
+  /// The maximum is updated after every successful send. It is tracked for at most
+  /// 32 queues at the same time, further queues always report 0. Owned queues stop
+  /// being tracked when they are dropped.
+  #[cfg(feature = "diagnostics")]
+  #[inline]
+  pub fn occupancy_high_water(&self) -> usize {
+    occupancy::get(self.as_ptr())
+  }
+}
+
+#[cfg(freertos_feature = "static_allocation")]
+impl<T> QueueHandle<T> {
+  /// Create a new queue which is stored in the given `buffer`.
+  ///
+  /// Both the queue structure and its items are placed inside `buffer`. The length
+  /// of the queue is the number of items fitting into the space remaining after
+  /// the queue structure, so it can be chosen at runtime.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `T` is zero-sized or if `buffer` cannot hold the queue structure and at least one item.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use core::{mem::MaybeUninit, time::Duration};
+  ///
+  /// use freertos_rust::sync::QueueHandle;
+  ///
+  /// let queue = QueueHandle::<u32>::new_in(unsafe {
+  ///   static mut BUFFER: [MaybeUninit<u8>; 256] = [MaybeUninit::uninit(); 256];
+  ///   &mut BUFFER
+  /// });
+  /// queue.send(42, Duration::MAX);
+  ///
+  /// assert_eq!(queue.receive(Duration::MAX), Ok(42));
+  /// ```
+  pub fn new_in(buffer: &'static mut [MaybeUninit<u8>]) -> &'static Self {
+    assert!(size_of::<T>() > 0);
+
+    let base = buffer.as_mut_ptr();
+    let queue_offset = base.align_offset(align_of::<StaticQueue_t>());
+    let items_offset = queue_offset + size_of::<StaticQueue_t>();
+    let items_offset = items_offset + base.wrapping_add(items_offset).align_offset(align_of::<T>());
+
+    let len = buffer.len().saturating_sub(items_offset) / size_of::<T>();
+    assert!(len > 0);
+
+    unsafe {
+      let queue_ptr = base.add(queue_offset).cast::<StaticQueue_t>();
+      let items_ptr = base.add(items_offset).cast();
+
+      let ptr = xQueueCreateStatic(
+        len as UBaseType_t,
+        size_of::<T>() as UBaseType_t,
+        items_ptr,
+        queue_ptr,
+      );
+      debug_assert!(!ptr.is_null());
+      debug_assert_eq!(ptr, queue_ptr as QueueHandle_t);
+
+      Self::from_ptr(ptr)
+    }
+  }
 }
 
 impl<T: Sized + Send> QueueHandle<T> {
+  /// Send an item to the end of the queue, returning the item if it could not be sent.
+  ///
+  /// Items are moved into the queue, so `T` does not need to be `Copy`.
+  /// If the queue is full, the item is returned to the caller together with the error.
+  #[inline]
+  pub fn send_owned(&self, item: T, timeout: impl Into<Ticks>) -> Result<(), (T, FreeRtosError)> {
+    unsafe {
+      let item = MaybeUninit::new(item);
+      let res = xQueueSend(self.as_ptr(), item.as_ptr().cast(), timeout.into().into());
+      match res {
+        pdTRUE => {
+          #[cfg(feature = "diagnostics")]
+          occupancy::record(self.as_ptr(), self.len());
+          Ok(())
+        },
+        errQUEUE_FULL => Err((item.assume_init(), FreeRtosError::QueueFull)),
+        _ => unreachable!(),
+      }
+    }
+  }
+
   /// Send an item to the end of the queue. Wait for the queue to have empty space for it.
   #[inline]
   pub fn send(&self, item: T, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
+    self.send_owned(item, timeout.into()).map_err(|(_, err)| err)
+  }
+
+  /// Send an item to the front of the queue. Wait for the queue to have empty space for it.
+  #[inline]
+  pub fn send_to_front(&self, item: T, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
     unsafe {
       let mut item = MaybeUninit::new(item);
-      let res = xQueueSend(self.as_ptr(), item.as_ptr().cast(), timeout.into().into());
+      let res = xQueueSendToFront(self.as_ptr(), item.as_ptr().cast(), timeout.into().into());
       match res {
-        pdTRUE => Ok(()),
+        pdTRUE => {
+          #[cfg(feature = "diagnostics")]
+          occupancy::record(self.as_ptr(), self.len());
+          Ok(())
+        },
         errQUEUE_FULL => {
           item.assume_init_drop();
           Err(FreeRtosError::QueueFull)
@@ -87,6 +274,47 @@ impl<T: Sized + Send> QueueHandle<T> {
     }
   }
 
+  /// Send an item to the end of the queue and return whether the queue was empty before.
+  ///
+  /// This allows a producer to only notify a consumer on the transition from
+  /// an empty to a non-empty queue. Checking the queue length and sending the item
+  /// happens atomically inside a critical section.
+  ///
+  /// If the queue is full, this blocks like [`send`](QueueHandle::send) until there is space
+  /// or the timeout expires. The queue length at the time the item is eventually enqueued
+  /// cannot be observed, so `Ok(true)` is returned in this case. This may cause a redundant
+  /// notification of the consumer, but never a missed one.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::{sync::Queue, FreeRtosError};
+  ///
+  /// let queue = Queue::<u32, 1>::new();
+  ///
+  /// assert_eq!(queue.send_detecting_wakeup(1, Duration::ZERO), Ok(true));
+  /// assert_eq!(queue.send_detecting_wakeup(2, Duration::ZERO), Err(FreeRtosError::QueueFull));
+  ///
+  /// assert_eq!(queue.receive(Duration::ZERO), Ok(1));
+  /// assert_eq!(queue.send_detecting_wakeup(3, Duration::ZERO), Ok(true));
+  /// ```
+  pub fn send_detecting_wakeup(&self, item: T, timeout: impl Into<Ticks>) -> Result<bool, FreeRtosError> {
+    let res = unsafe {
+      freertos_rs_enter_critical();
+      let was_empty = self.len() == 0;
+      let res = self.send_owned(item, Ticks::new(0)).map(|()| was_empty);
+      freertos_rs_exit_critical();
+      res
+    };
+
+    match res {
+      Ok(was_empty) => Ok(was_empty),
+      Err((item, _)) => self.send(item, timeout).map(|()| true),
+    }
+  }
+
   /// Send an item to the end of the queue, from an interrupt.
   #[inline]
   pub fn send_from_isr(
@@ -100,7 +328,11 @@ impl<T: Sized + Send> QueueHandle<T> {
       let res = xQueueSendFromISR(self.as_ptr(), item.as_ptr().cast(), ic.as_ptr());
 
       match res {
-        pdTRUE => Ok(()),
+        pdTRUE => {
+          #[cfg(feature = "diagnostics")]
+          occupancy::record(self.as_ptr(), self.len_from_isr(ic));
+          Ok(())
+        },
         errQUEUE_FULL => {
           item.assume_init_drop();
           Err(FreeRtosError::QueueFull)
@@ -110,6 +342,56 @@ impl<T: Sized + Send> QueueHandle<T> {
     }
   }
 
+  /// Overwrite the item in the queue, dropping the previously stored item, if any.
+  ///
+  /// # Safety
+  ///
+  /// The queue must have a length of 1.
+  pub(crate) unsafe fn overwrite_unchecked(&self, item: T) {
+    let item = MaybeUninit::new(item);
+    let mut old_item = MaybeUninit::<T>::uninit();
+
+    freertos_rs_enter_critical();
+    let has_old_item = mem::needs_drop::<T>() &&
+      xQueueReceive(self.as_ptr(), old_item.as_mut_ptr().cast(), 0) == pdTRUE;
+    let res = xQueueOverwrite(self.as_ptr(), item.as_ptr().cast());
+    freertos_rs_exit_critical();
+
+    debug_assert_eq!(res, pdPASS);
+
+    #[cfg(feature = "diagnostics")]
+    occupancy::record(self.as_ptr(), 1);
+
+    if has_old_item {
+      old_item.assume_init_drop();
+    }
+  }
+
+  /// Overwrite the item in the queue from an interrupt, dropping the previously stored item, if any.
+  ///
+  /// # Safety
+  ///
+  /// The queue must have a length of 1.
+  pub(crate) unsafe fn overwrite_from_isr_unchecked(&self, ic: &InterruptContext, item: T) {
+    let item = MaybeUninit::new(item);
+    let mut old_item = MaybeUninit::<T>::uninit();
+
+    let saved_interrupt_status = freertos_rs_enter_critical_from_isr();
+    let has_old_item = mem::needs_drop::<T>() &&
+      xQueueReceiveFromISR(self.as_ptr(), old_item.as_mut_ptr().cast(), ic.as_ptr()) == pdTRUE;
+    let res = xQueueOverwriteFromISR(self.as_ptr(), item.as_ptr().cast(), ic.as_ptr());
+    freertos_rs_exit_critical_from_isr(saved_interrupt_status);
+
+    debug_assert_eq!(res, pdPASS);
+
+    #[cfg(feature = "diagnostics")]
+    occupancy::record(self.as_ptr(), 1);
+
+    if has_old_item {
+      old_item.assume_init_drop();
+    }
+  }
+
   /// Wait for an item to be available on the queue.
   #[inline]
   pub fn receive(&self, timeout: impl Into<Ticks>) -> Result<T, FreeRtosError> {
@@ -123,4 +405,178 @@ impl<T: Sized + Send> QueueHandle<T> {
       }
     }
   }
+
+  /// Send an item to the front of the queue, from an interrupt.
+  #[inline]
+  pub fn send_to_front_from_isr(
+      &self,
+      ic: &InterruptContext,
+      item: T,
+  ) -> Result<(), FreeRtosError> {
+    unsafe {
+      let mut item: MaybeUninit<T> = MaybeUninit::new(item);
+      let res = xQueueSendToFrontFromISR(self.as_ptr(), item.as_ptr().cast(), ic.as_ptr());
+
+      match res {
+        pdTRUE => {
+          #[cfg(feature = "diagnostics")]
+          occupancy::record(self.as_ptr(), self.len_from_isr(ic));
+          Ok(())
+        },
+        errQUEUE_FULL => {
+          item.assume_init_drop();
+          Err(FreeRtosError::QueueFull)
+        },
+        _ => unreachable!(),
+      }
+    }
+  }
+
+  /// Remove all items from the queue.
+  ///
+  /// If `T` does not need to be dropped, the queue is reset using `xQueueReset`.
+  /// Otherwise, the queue is drained so that every removed item is dropped.
+  ///
+  /// This must not be called from an interrupt service routine, since `xQueueReset`
+  /// is not interrupt-safe. Use [`drain_from_isr`](QueueHandle::drain_from_isr) instead.
+  pub fn reset(&self) {
+    if mem::needs_drop::<T>() {
+      while let Ok(item) = self.receive(Ticks::new(0)) {
+        drop(item);
+      }
+    } else {
+      unsafe { xQueueReset(self.as_ptr()) };
+    }
+  }
+
+  /// Wait for an item to be available on the queue without receiving it.
+  ///
+  /// Note that there is no equivalent for waiting until the queue has space without
+  /// sending an item. This needs to be implemented using a counting semaphore
+  /// mirroring the number of free spaces in the queue.
+  #[inline]
+  pub fn wait_for_item(&self, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
+    // The item is only copied into this buffer to satisfy `xQueuePeek`, it is
+    // never treated as an initialized `T`, so `T` does not need to be `Copy`.
+    let mut item = MaybeUninit::<T>::uninit();
+
+    match unsafe { xQueuePeek(self.as_ptr(), item.as_mut_ptr().cast(), timeout.into().into()) } {
+      pdTRUE => Ok(()),
+      _ => Err(FreeRtosError::Timeout),
+    }
+  }
+
+  /// Get an iterator which blocks until the next item is received.
+  ///
+  /// The iterator never ends, so this is useful for tasks which consume a queue forever.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use freertos_rust::sync::Queue;
+  ///
+  /// let queue = Queue::<u32, 8>::new();
+  ///
+  /// for item in queue.iter_blocking() {
+  ///   println!("Received {item}.");
+  /// }
+  /// ```
+  #[inline]
+  pub fn iter_blocking(&self) -> IterBlocking<'_, T> {
+    IterBlocking { queue: self }
+  }
+
+  /// Get an iterator which receives all items currently in the queue without blocking.
+  ///
+  /// The iterator ends as soon as the queue is empty.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::sync::Queue;
+  ///
+  /// let queue = Queue::<u32, 8>::new();
+  /// queue.send(1, Duration::ZERO).unwrap();
+  /// queue.send(2, Duration::ZERO).unwrap();
+  ///
+  /// assert_eq!(queue.drain().sum::<u32>(), 3);
+  /// assert!(queue.is_empty());
+  /// ```
+  #[inline]
+  pub fn drain(&self) -> Drain<'_, T> {
+    Drain { queue: self, done: false }
+  }
+
+  /// Receive an item from the queue, from an interrupt.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Unavailable`] if the queue is empty.
+  #[inline]
+  pub fn receive_from_isr(&self, ic: &InterruptContext) -> Result<T, FreeRtosError> {
+    let mut item = MaybeUninit::<T>::uninit();
+
+    unsafe {
+      match xQueueReceiveFromISR(self.as_ptr(), item.as_mut_ptr().cast(), ic.as_ptr()) {
+        pdTRUE => Ok(item.assume_init()),
+        _ => Err(FreeRtosError::Unavailable),
+      }
+    }
+  }
+
+  /// Remove and drop all items from the queue, from an interrupt.
+  ///
+  /// Returns the number of removed items.
+  pub fn drain_from_isr(&self, ic: &InterruptContext) -> usize {
+    let mut count = 0;
+
+    while let Ok(item) = self.receive_from_isr(ic) {
+      drop(item);
+      count += 1;
+    }
+
+    count
+  }
+
+  /// Wait for an item to be available on the queue, returning `default` on timeout.
+  #[inline]
+  pub fn receive_or(&self, default: T, timeout: impl Into<Ticks>) -> T {
+    self.receive(timeout).unwrap_or(default)
+  }
+
+  /// Wait for an item to be available on the queue, returning the result of `f` on timeout.
+  #[inline]
+  pub fn receive_or_else(&self, f: impl FnOnce() -> T, timeout: impl Into<Ticks>) -> T {
+    self.receive(timeout).unwrap_or_else(|_| f())
+  }
+}
+
+impl<T: Sized + Send + Copy> QueueHandle<T> {
+  /// Wait for an item to be available on the queue and copy it without removing it.
+  #[inline]
+  pub fn peek(&self, timeout: impl Into<Ticks>) -> Result<T, FreeRtosError> {
+    let mut item = MaybeUninit::<T>::uninit();
+
+    unsafe {
+      match xQueuePeek(self.as_ptr(), item.as_mut_ptr().cast(), timeout.into().into()) {
+        pdTRUE => Ok(item.assume_init()),
+        _ => Err(FreeRtosError::Timeout),
+      }
+    }
+  }
+
+  /// Copy the item at the front of the queue without removing it, from an interrupt.
+  #[inline]
+  pub fn peek_from_isr(&self, _ic: &InterruptContext) -> Result<T, FreeRtosError> {
+    let mut item = MaybeUninit::<T>::uninit();
+
+    unsafe {
+      match xQueuePeekFromISR(self.as_ptr(), item.as_mut_ptr().cast()) {
+        pdTRUE => Ok(item.assume_init()),
+        _ => Err(FreeRtosError::Unavailable),
+      }
+    }
+  }
 }
@@ -0,0 +1,60 @@
+use core::iter::FusedIterator;
+
+use crate::{shim::portMAX_DELAY, Ticks};
+
+use super::QueueHandle;
+
+/// A blocking iterator over items received from a queue.
+///
+/// Returned by [`QueueHandle::iter_blocking`].
+#[derive(Debug)]
+pub struct IterBlocking<'q, T> {
+  pub(super) queue: &'q QueueHandle<T>,
+}
+
+impl<T: Send> Iterator for IterBlocking<'_, T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    loop {
+      if let Ok(item) = self.queue.receive(Ticks::new(portMAX_DELAY)) {
+        return Some(item)
+      }
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (usize::MAX, None)
+  }
+}
+
+impl<T: Send> FusedIterator for IterBlocking<'_, T> {}
+
+/// A non-blocking iterator which receives all items currently available in a queue.
+///
+/// Returned by [`QueueHandle::drain`].
+#[derive(Debug)]
+pub struct Drain<'q, T> {
+  pub(super) queue: &'q QueueHandle<T>,
+  pub(super) done: bool,
+}
+
+impl<T: Send> Iterator for Drain<'_, T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    if self.done {
+      return None
+    }
+
+    let item = self.queue.receive(Ticks::new(0)).ok();
+    self.done = item.is_none();
+    item
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, None)
+  }
+}
+
+impl<T: Send> FusedIterator for Drain<'_, T> {}
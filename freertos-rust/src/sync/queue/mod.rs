@@ -1,11 +1,14 @@
 use core::{
+  ffi::CStr,
   marker::PhantomData,
-  mem::{MaybeUninit, size_of, self},
+  mem::{MaybeUninit, size_of},
   ops::Deref,
   ptr,
 };
 
 use crate::{
+  FreeRtosError,
+  InterruptContext,
   ffi::{QueueHandle_t, UBaseType_t},
   shim::{vQueueDelete, StaticQueue_t},
 };
@@ -16,8 +19,16 @@ use crate::shim::xQueueCreateStatic;
 
 mod handle;
 pub use handle::QueueHandle;
-
-/// A fixed-size queue. Items are copied and owned by the queue.
+mod iter;
+pub use iter::{Drain, IterBlocking};
+#[cfg(feature = "diagnostics")]
+mod occupancy;
+mod weak;
+pub use weak::WeakSender;
+
+/// A fixed-size queue. Items are moved into and owned by the queue.
+///
+/// Items remaining in the queue are dropped when the queue is dropped.
 ///
 /// # Example
 ///
@@ -44,23 +55,65 @@ unsafe impl<T: Send, const SIZE: usize> Sync for Queue<T, SIZE> {}
 #[cfg(freertos_feature = "dynamic_allocation")]
 impl<T, const SIZE: usize> Queue<T, SIZE> {
     /// Create a new dynamic queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`try_new`](Self::try_new) fails.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-      let ptr = unsafe {
-        xQueueCreate(
-          (mem::size_of::<T>() * SIZE) as UBaseType_t,
-          size_of::<T>() as UBaseType_t,
-        )
+      Self::try_new().unwrap()
+    }
+
+    /// Try creating a new dynamic queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FreeRtosError::InvalidParameter`] if `SIZE` is zero or the queue's length
+    /// or item size do not fit into a `UBaseType_t`, and [`FreeRtosError::OutOfMemory`] if
+    /// the queue could not be allocated.
+    pub fn try_new() -> Result<Self, FreeRtosError> {
+      let (Ok(length), Ok(item_size)) = (UBaseType_t::try_from(SIZE), UBaseType_t::try_from(size_of::<T>())) else {
+        return Err(FreeRtosError::InvalidParameter)
       };
-      assert!(!ptr.is_null());
 
-      Self {
-        handle: ptr,
-        item_type: PhantomData,
+      if length == 0 {
+        return Err(FreeRtosError::InvalidParameter)
+      }
+
+      let ptr = unsafe { xQueueCreate(length, item_size) };
+      if ptr.is_null() {
+        return Err(FreeRtosError::OutOfMemory)
       }
+
+      Ok(Self { handle: ptr, item_type: PhantomData })
+    }
+
+    /// Create a new dynamic queue and add it to the registry with the given name.
+    ///
+    /// The queue is removed from the registry when it is dropped.
+    pub fn new_named(name: &'static CStr) -> Self {
+      let queue = Self::new();
+      queue.add_to_registry(name);
+      queue
     }
 }
 
+impl<T: Send> Queue<T, 1> {
+  /// Overwrite the item in the queue, dropping the previously stored item, if any.
+  ///
+  /// This always succeeds and is useful for mailbox-style queues.
+  #[inline]
+  pub fn overwrite(&self, item: T) {
+    unsafe { self.overwrite_unchecked(item) }
+  }
+
+  /// Overwrite the item in the queue from an interrupt, dropping the previously stored item, if any.
+  #[inline]
+  pub fn overwrite_from_isr(&self, ic: &InterruptContext, item: T) {
+    unsafe { self.overwrite_from_isr_unchecked(ic, item) }
+  }
+}
+
 impl<T, const SIZE: usize> Deref for Queue<T, SIZE> {
   type Target = QueueHandle<T>;
 
@@ -71,7 +124,12 @@ impl<T, const SIZE: usize> Deref for Queue<T, SIZE> {
 
 impl<T, const SIZE: usize> Drop for Queue<T, SIZE> {
   fn drop(&mut self) {
-    unsafe { vQueueDelete(self.handle) }
+    unsafe {
+      self.drop_items();
+      #[cfg(feature = "diagnostics")]
+      occupancy::release(self.handle);
+      vQueueDelete(self.handle)
+    }
   }
 }
 
@@ -120,6 +178,22 @@ impl<T, const SIZE: usize> StaticQueue<T, SIZE> {
   }
 }
 
+impl<T: Send> StaticQueue<T, 1> {
+  /// Overwrite the item in the queue, dropping the previously stored item, if any.
+  ///
+  /// This always succeeds and is useful for mailbox-style queues.
+  #[inline]
+  pub fn overwrite(&self, item: T) {
+    unsafe { self.overwrite_unchecked(item) }
+  }
+
+  /// Overwrite the item in the queue from an interrupt, dropping the previously stored item, if any.
+  #[inline]
+  pub fn overwrite_from_isr(&self, ic: &InterruptContext, item: T) {
+    unsafe { self.overwrite_from_isr_unchecked(ic, item) }
+  }
+}
+
 impl<T, const SIZE: usize> Deref for StaticQueue<T, SIZE> {
   type Target = QueueHandle<T>;
 
@@ -130,6 +204,11 @@ impl<T, const SIZE: usize> Deref for StaticQueue<T, SIZE> {
 
 impl<T, const SIZE: usize> Drop for StaticQueue<T, SIZE> {
   fn drop(&mut self) {
-    unsafe { vQueueDelete(self.as_ptr()) }
+    unsafe {
+      self.drop_items();
+      #[cfg(feature = "diagnostics")]
+      occupancy::release(self.as_ptr());
+      vQueueDelete(self.as_ptr())
+    }
   }
 }
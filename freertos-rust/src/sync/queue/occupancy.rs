@@ -0,0 +1,67 @@
+//! Occupancy high water marks of queues.
+//!
+//! A queue handle is the FreeRTOS queue structure itself, so there is no room to store
+//! the high water mark next to it. Instead, it is kept in a fixed-size table keyed by
+//! the queue handle, which is updated by every send function of [`QueueHandle`](super::QueueHandle).
+
+use core::{
+  ffi::c_void,
+  ptr,
+  sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+
+use crate::ffi::QueueHandle_t;
+
+/// The maximum number of queues for which the occupancy is tracked at the same time.
+const MAX_TRACKED_QUEUES: usize = 32;
+
+struct Slot {
+  queue: AtomicPtr<c_void>,
+  high_water: AtomicUsize,
+}
+
+impl Slot {
+  const fn new() -> Self {
+    Self { queue: AtomicPtr::new(ptr::null_mut()), high_water: AtomicUsize::new(0) }
+  }
+}
+
+static SLOTS: [Slot; MAX_TRACKED_QUEUES] = [const { Slot::new() }; MAX_TRACKED_QUEUES];
+
+fn slots_for(queue: QueueHandle_t) -> impl Iterator<Item = &'static Slot> {
+  SLOTS.iter().filter(move |slot| slot.queue.load(Ordering::Acquire) == queue.cast())
+}
+
+/// Record that `queue` currently contains `len` items.
+///
+/// If `queue` is not tracked yet, a free slot is claimed for it. If there is none,
+/// the occupancy of `queue` is not tracked.
+pub(super) fn record(queue: QueueHandle_t, len: usize) {
+  if let Some(slot) = slots_for(queue).next() {
+    slot.high_water.fetch_max(len, Ordering::Relaxed);
+    return
+  }
+
+  // Concurrent first sends may claim more than one slot for the same queue,
+  // which is handled by taking the maximum over all of them in `get`.
+  let claimed = SLOTS.iter().find(|slot| {
+    slot.queue.compare_exchange(ptr::null_mut(), queue.cast(), Ordering::AcqRel, Ordering::Relaxed).is_ok()
+  });
+
+  if let Some(slot) = claimed {
+    slot.high_water.fetch_max(len, Ordering::Relaxed);
+  }
+}
+
+/// Get the occupancy high water mark of `queue`.
+pub(super) fn get(queue: QueueHandle_t) -> usize {
+  slots_for(queue).map(|slot| slot.high_water.load(Ordering::Relaxed)).max().unwrap_or(0)
+}
+
+/// Stop tracking `queue`, e.g. because it is deleted.
+pub(super) fn release(queue: QueueHandle_t) {
+  for slot in slots_for(queue) {
+    slot.high_water.store(0, Ordering::Relaxed);
+    slot.queue.store(ptr::null_mut(), Ordering::Release);
+  }
+}
@@ -0,0 +1,69 @@
+use alloc2::sync::{Arc, Weak};
+
+use crate::{FreeRtosError, Ticks};
+
+use super::Queue;
+
+/// A sender for a shared [`Queue`] which does not keep the queue alive.
+///
+/// This is useful for registering observers or callbacks which should not
+/// extend the lifetime of the receiving side.
+///
+/// # Examples
+///
+/// ```
+/// extern crate alloc;
+/// use alloc::sync::Arc;
+/// use core::time::Duration;
+///
+/// use freertos_rust::{sync::{Queue, WeakSender}, FreeRtosError};
+///
+/// let queue = Arc::new(Queue::<u32, 4>::new());
+/// let sender = WeakSender::new(&queue);
+///
+/// sender.send(1, Duration::ZERO).unwrap();
+/// assert_eq!(queue.receive(Duration::ZERO), Ok(1));
+///
+/// drop(queue);
+/// assert_eq!(sender.send(2, Duration::ZERO), Err(FreeRtosError::Disconnected));
+/// ```
+pub struct WeakSender<T, const SIZE: usize> {
+  queue: Weak<Queue<T, SIZE>>,
+}
+
+impl<T, const SIZE: usize> Clone for WeakSender<T, SIZE> {
+  fn clone(&self) -> Self {
+    Self { queue: Weak::clone(&self.queue) }
+  }
+}
+
+impl<T, const SIZE: usize> WeakSender<T, SIZE> {
+  /// Create a new weak sender for the given `queue`.
+  pub fn new(queue: &Arc<Queue<T, SIZE>>) -> Self {
+    Self { queue: Arc::downgrade(queue) }
+  }
+
+  /// Get a strong reference to the queue if it still exists.
+  #[inline]
+  pub fn upgrade(&self) -> Option<Arc<Queue<T, SIZE>>> {
+    self.queue.upgrade()
+  }
+
+  /// Check whether the queue has been dropped.
+  #[inline]
+  pub fn is_disconnected(&self) -> bool {
+    self.queue.strong_count() == 0
+  }
+}
+
+impl<T: Send, const SIZE: usize> WeakSender<T, SIZE> {
+  /// Send an item to the end of the queue. Wait for the queue to have empty space for it.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Disconnected`] if the queue has been dropped.
+  pub fn send(&self, item: T, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
+    let queue = self.upgrade().ok_or(FreeRtosError::Disconnected)?;
+    queue.send(item, timeout)
+  }
+}
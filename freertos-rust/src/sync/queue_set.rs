@@ -0,0 +1,170 @@
+use core::{fmt, ptr};
+
+use crate::{
+  ffi::{QueueSetHandle_t, QueueSetMemberHandle_t, UBaseType_t},
+  shim::{pdPASS, vQueueDelete, xQueueAddToSet, xQueueRemoveFromSet, xQueueSelectFromSet, xQueueSelectFromSetFromISR},
+  sync::{QueueHandle, SemaphoreHandle},
+  FreeRtosError,
+  InterruptContext,
+  Ticks,
+};
+#[cfg(freertos_feature = "dynamic_allocation")]
+use crate::shim::xQueueCreateSet;
+
+/// A queue or semaphore which can be added to a [`QueueSet`].
+///
+/// # Safety
+///
+/// [`as_member_ptr`](QueueSetMember::as_member_ptr) must return a valid queue or semaphore handle.
+pub unsafe trait QueueSetMember {
+  /// Get the raw handle of this member.
+  fn as_member_ptr(&self) -> QueueSetMemberHandle_t;
+}
+
+unsafe impl<T> QueueSetMember for QueueHandle<T> {
+  #[inline]
+  fn as_member_ptr(&self) -> QueueSetMemberHandle_t {
+    self.as_ptr()
+  }
+}
+
+unsafe impl QueueSetMember for SemaphoreHandle {
+  #[inline]
+  fn as_member_ptr(&self) -> QueueSetMemberHandle_t {
+    self.as_ptr()
+  }
+}
+
+/// A queue set for blocking on multiple queues and semaphores at once.
+///
+/// `SIZE` must be at least the sum of the lengths of all queues and the maximum counts
+/// of all semaphores in the set. A member can only be added to or removed from a set while
+/// it is empty and a member must not be received from or taken without first being selected.
+///
+/// The set must not be deleted before all members have been removed from it.
+///
+/// This requires `configUSE_QUEUE_SETS`.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use freertos_rust::sync::{Queue, QueueSet, Semaphore};
+///
+/// let queue = Queue::<u32, 4>::new();
+/// let semaphore = Semaphore::new_binary();
+///
+/// let set = QueueSet::<5>::new();
+/// set.add(&*queue).unwrap();
+/// set.add(&*semaphore).unwrap();
+///
+/// queue.send(42, Duration::MAX).unwrap();
+///
+/// let member = set.select(Duration::MAX).unwrap();
+/// if member.is(&*queue) {
+///   assert_eq!(queue.receive(Duration::ZERO), Ok(42));
+/// } else if member.is(&*semaphore) {
+///   semaphore.take(Duration::ZERO).unwrap();
+/// }
+/// ```
+pub struct QueueSet<const SIZE: usize> {
+  handle: QueueSetHandle_t,
+}
+
+unsafe impl<const SIZE: usize> Send for QueueSet<SIZE> {}
+unsafe impl<const SIZE: usize> Sync for QueueSet<SIZE> {}
+
+impl<const SIZE: usize> fmt::Debug for QueueSet<SIZE> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.handle.fmt(f)
+  }
+}
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+impl<const SIZE: usize> QueueSet<SIZE> {
+  /// Create a new dynamic queue set.
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    assert!(SIZE > 0);
+
+    let ptr = unsafe { xQueueCreateSet(SIZE as UBaseType_t) };
+    assert!(!ptr.is_null());
+
+    Self { handle: ptr }
+  }
+}
+
+impl<const SIZE: usize> QueueSet<SIZE> {
+  /// Get the raw queue set handle.
+  #[inline]
+  pub const fn as_ptr(&self) -> QueueSetHandle_t {
+    self.handle
+  }
+
+  /// Add a queue or semaphore to the set.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if the member is not empty
+  /// or is already part of a set.
+  pub fn add<M: QueueSetMember + ?Sized>(&self, member: &M) -> Result<(), FreeRtosError> {
+    match unsafe { xQueueAddToSet(member.as_member_ptr(), self.handle) } {
+      pdPASS => Ok(()),
+      _ => Err(FreeRtosError::InvalidParameter),
+    }
+  }
+
+  /// Remove a queue or semaphore from the set.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if the member is not empty
+  /// or is not part of this set.
+  pub fn remove<M: QueueSetMember + ?Sized>(&self, member: &M) -> Result<(), FreeRtosError> {
+    match unsafe { xQueueRemoveFromSet(member.as_member_ptr(), self.handle) } {
+      pdPASS => Ok(()),
+      _ => Err(FreeRtosError::InvalidParameter),
+    }
+  }
+
+  /// Wait for a member of the set to contain data or become available.
+  ///
+  /// Returns `None` if the timeout expired.
+  pub fn select(&self, timeout: impl Into<Ticks>) -> Option<SetMember> {
+    let ptr = unsafe { xQueueSelectFromSet(self.handle, timeout.into().into()) };
+    (!ptr.is_null()).then_some(SetMember { ptr })
+  }
+
+  /// Get a member of the set which contains data or is available, from an interrupt.
+  pub fn select_from_isr(&self, _ic: &InterruptContext) -> Option<SetMember> {
+    let ptr = unsafe { xQueueSelectFromSetFromISR(self.handle) };
+    (!ptr.is_null()).then_some(SetMember { ptr })
+  }
+}
+
+impl<const SIZE: usize> Drop for QueueSet<SIZE> {
+  fn drop(&mut self) {
+    unsafe { vQueueDelete(self.handle) }
+  }
+}
+
+/// A member of a [`QueueSet`] returned by [`QueueSet::select`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetMember {
+  ptr: QueueSetMemberHandle_t,
+}
+
+impl SetMember {
+  /// Check whether this is the given queue or semaphore.
+  #[inline]
+  pub fn is<M: QueueSetMember + ?Sized>(&self, member: &M) -> bool {
+    ptr::eq(self.ptr, member.as_member_ptr())
+  }
+
+  /// Get the raw handle of this member.
+  #[inline]
+  pub const fn as_ptr(&self) -> QueueSetMemberHandle_t {
+    self.ptr
+  }
+}
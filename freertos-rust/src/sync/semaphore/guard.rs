@@ -1,3 +1,5 @@
+use crate::InterruptContext;
+
 use super::SemaphoreHandle;
 
 /// An RAII implementation of a “scoped decrement” of a semaphore.
@@ -16,3 +18,18 @@ impl Drop for SemaphoreGuard<'_> {
     let _ = self.handle.give();
   }
 }
+
+/// An RAII implementation of a “scoped decrement” of a semaphore inside an interrupt service routine.
+///
+/// When this structure is dropped (falls out of scope), the semaphore is incremented again.
+#[must_use = concat!("if unused the `Semaphore` will increment again immediately")]
+pub struct IsrSemaphoreGuard<'ic, 's> {
+  pub(super) ic: &'ic InterruptContext,
+  pub(super) handle: &'s SemaphoreHandle,
+}
+
+impl Drop for IsrSemaphoreGuard<'_, '_> {
+  fn drop(&mut self) {
+    let _ = self.handle.give_from_isr(self.ic);
+  }
+}
@@ -1,4 +1,4 @@
-use core::{fmt, ptr};
+use core::{ffi::CStr, fmt, ptr};
 
 use crate::{
   FreeRtosError,
@@ -9,6 +9,10 @@ use crate::{
     errQUEUE_FULL,
     pdFALSE,
     pdTRUE,
+    uxQueueMessagesWaitingFromISR,
+    uxSemaphoreGetCount,
+    vQueueAddToRegistry,
+    vQueueUnregisterQueue,
     xSemaphoreGive,
     xSemaphoreGiveFromISR,
     xSemaphoreGiveRecursive,
@@ -18,7 +22,7 @@ use crate::{
   },
 };
 
-use super::SemaphoreGuard;
+use super::{SemaphoreGuard, IsrSemaphoreGuard};
 
 /// A handle for managing a binary or counting semaphore.
 ///
@@ -53,6 +57,34 @@ impl SemaphoreHandle {
     ptr::addr_of!(self.0).cast_mut()
   }
 
+  /// Assign a name to the semaphore and add it to the registry.
+  #[inline]
+  pub fn add_to_registry(&self, name: &'static CStr) {
+    unsafe { vQueueAddToRegistry(self.as_ptr(), name.as_ptr()) }
+  }
+
+  /// Remove the semaphore from the registry.
+  ///
+  /// Deleting a semaphore automatically removes it from the registry.
+  #[inline]
+  pub fn remove_from_registry(&self) {
+    unsafe { vQueueUnregisterQueue(self.as_ptr()) }
+  }
+
+  /// Get the current count of the semaphore.
+  ///
+  /// For a binary semaphore, this is `1` if the semaphore is available and `0` otherwise.
+  #[inline]
+  pub fn count(&self) -> usize {
+    unsafe { uxSemaphoreGetCount(self.as_ptr()) as usize }
+  }
+
+  /// Get the current count of the semaphore from within an interrupt service routine.
+  #[inline]
+  pub fn count_from_isr(&self, _ic: &InterruptContext) -> usize {
+    unsafe { uxQueueMessagesWaitingFromISR(self.as_ptr()) as usize }
+  }
+
   /// Increment the semaphore.
   #[inline]
   pub fn give(&self) -> Result<(), FreeRtosError> {
@@ -74,10 +106,33 @@ impl SemaphoreHandle {
   }
 
   /// Increment the semaphore or unlock the mutex from within an interrupt service routine.
+  ///
+  /// Returns whether giving the semaphore woke a task with a higher priority than the
+  /// interrupted task. This is also accumulated in `ic`, so when giving multiple semaphores,
+  /// a single context switch is requested when `ic` is dropped.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use freertos_rust::{InterruptContext, sync::{Binary, Semaphore}};
+  ///
+  /// fn isr(a: &Semaphore<Binary>, b: &Semaphore<Binary>) {
+  ///   let ic = InterruptContext::new();
+  ///
+  ///   let _ = a.give_from_isr(&ic);
+  ///   let _ = b.give_from_isr(&ic);
+  ///
+  ///   if ic.should_yield() {
+  ///     // A context switch is requested once when `ic` is dropped.
+  ///   }
+  /// }
+  /// ```
   #[inline]
-  pub fn give_from_isr(&self, ic: &InterruptContext) -> Result<(), FreeRtosError> {
-    match unsafe { xSemaphoreGiveFromISR(self.as_ptr(), ic.as_ptr()) } {
-      pdTRUE => Ok(()),
+  pub fn give_from_isr(&self, ic: &InterruptContext) -> Result<bool, FreeRtosError> {
+    let (res, woken) = ic.track_woken(|woken| unsafe { xSemaphoreGiveFromISR(self.as_ptr(), woken) });
+
+    match res {
+      pdTRUE => Ok(woken),
       errQUEUE_FULL => Err(FreeRtosError::QueueFull),
       _ => unreachable!(),
     }
@@ -104,10 +159,15 @@ impl SemaphoreHandle {
   }
 
   /// Decrement the semaphore or lock the mutex from within an interrupt service routine.
+  ///
+  /// Returns whether taking the semaphore woke a task with a higher priority than the
+  /// interrupted task, see [`give_from_isr`](SemaphoreHandle::give_from_isr).
   #[inline]
-  pub fn take_from_isr(&self, ic: &InterruptContext) -> Result<(), FreeRtosError> {
-    match unsafe { xSemaphoreTakeFromISR(self.as_ptr(), ic.as_ptr()) } {
-      pdTRUE => Ok(()),
+  pub fn take_from_isr(&self, ic: &InterruptContext) -> Result<bool, FreeRtosError> {
+    let (res, woken) = ic.track_woken(|woken| unsafe { xSemaphoreTakeFromISR(self.as_ptr(), woken) });
+
+    match res {
+      pdTRUE => Ok(woken),
       pdFALSE => Err(FreeRtosError::Unavailable),
       _ => unreachable!(),
     }
@@ -119,4 +179,42 @@ impl SemaphoreHandle {
 
     Ok(SemaphoreGuard { handle: self })
   }
+
+  /// Try locking this semaphore in RAII fashion without blocking.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Unavailable`] if the semaphore cannot be decremented.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use freertos_rust::{sync::{Counting, Semaphore}, FreeRtosError};
+  ///
+  /// let pool = Semaphore::<Counting<1, 1>>::new_counting();
+  ///
+  /// let permit = pool.try_lock().unwrap();
+  /// assert_eq!(pool.try_lock().unwrap_err(), FreeRtosError::Unavailable);
+  ///
+  /// drop(permit);
+  /// assert!(pool.try_lock().is_ok());
+  /// ```
+  pub fn try_lock(&self) -> Result<SemaphoreGuard<'_>, FreeRtosError> {
+    match self.take(Ticks::new(0)) {
+      Ok(()) => Ok(SemaphoreGuard { handle: self }),
+      Err(FreeRtosError::Timeout) => Err(FreeRtosError::Unavailable),
+      Err(err) => Err(err),
+    }
+  }
+
+  /// Lock this semaphore in RAII fashion from within an interrupt service routine.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Unavailable`] if the semaphore cannot be decremented.
+  pub fn lock_from_isr<'ic>(&self, ic: &'ic InterruptContext) -> Result<IsrSemaphoreGuard<'ic, '_>, FreeRtosError> {
+    self.take_from_isr(ic)?;
+
+    Ok(IsrSemaphoreGuard { ic, handle: self })
+  }
 }
@@ -1,15 +1,17 @@
+use core::ffi::CStr;
 use core::marker::PhantomData;
 use core::mem::MaybeUninit;
 use core::ptr;
 use core::ops::Deref;
 
 mod guard;
-pub use guard::SemaphoreGuard;
+pub use guard::{SemaphoreGuard, IsrSemaphoreGuard};
 mod handle;
 pub use handle::SemaphoreHandle;
 
 use crate::{
-  ffi::SemaphoreHandle_t,
+  FreeRtosError,
+  ffi::{SemaphoreHandle_t, UBaseType_t},
   shim::{
     StaticSemaphore_t,
     vSemaphoreDelete,
@@ -30,6 +32,10 @@ pub struct Counting<const INITIAL: usize, const MAX: usize> {}
 
 /// A binary or counting semaphore.
 ///
+/// Binary semaphores are created empty, i.e. they must be given before the first
+/// `take` succeeds. Use [`new_binary_given`](Semaphore::new_binary_given) to create
+/// a binary semaphore which is initially available.
+///
 /// # Example
 ///
 /// ```
@@ -58,18 +64,49 @@ macro_rules! impl_semaphore {
     $semaphore:ident $(<const $initial:ident: $initial_ty:ident = $initial_val:literal, const $max:ident: $max_ty:ident = $max_val:literal>)?,
     $create:ident,
     $new_fn:ident,
+    $try_new_fn:ident,
+    $new_named_fn:ident,
     $variant_name:ident,
   ) => {
     #[cfg(freertos_feature = "dynamic_allocation")]
     impl<$(const $initial: $initial_ty, const $max: $max_ty)*> Semaphore<$semaphore$(<$initial, $max>)*> {
       #[doc = concat!("Create a new dynamic ", stringify!($variant_name), " semaphore.")]
+      ///
+      /// # Panics
+      ///
+      #[doc = concat!("Panics if [`", stringify!($try_new_fn), "`](Self::", stringify!($try_new_fn), ") fails.")]
       pub fn $new_fn() -> Self {
-        $(assert!($initial <= $max);)*
+        Self::$try_new_fn().unwrap()
+      }
+
+      #[doc = concat!("Try creating a new dynamic ", stringify!($variant_name), " semaphore.")]
+      ///
+      /// # Errors
+      ///
+      /// Returns [`FreeRtosError::InvalidParameter`] if the maximum count is zero or smaller than
+      /// the initial count and [`FreeRtosError::OutOfMemory`] if the semaphore could not be allocated.
+      pub fn $try_new_fn() -> Result<Self, FreeRtosError> {
+        $(
+          if $max == 0 || $initial > $max || UBaseType_t::try_from($max).is_err() {
+            return Err(FreeRtosError::InvalidParameter)
+          }
+        )*
 
         let ptr = unsafe { $create($($max as _, $initial as _)*) };
-        assert!(!ptr.is_null());
+        if ptr.is_null() {
+          return Err(FreeRtosError::OutOfMemory)
+        }
+
+        Ok(Self { handle: ptr, mode: PhantomData })
+      }
 
-        Self { handle: ptr, mode: PhantomData }
+      #[doc = concat!("Create a new dynamic ", stringify!($variant_name), " semaphore and add it to the registry with the given name.")]
+      ///
+      /// The semaphore is removed from the registry when it is dropped.
+      pub fn $new_named_fn(name: &'static CStr) -> Self {
+        let semaphore = Self::$new_fn();
+        semaphore.add_to_registry(name);
+        semaphore
       }
     }
   };
@@ -81,6 +118,8 @@ impl_semaphore!(
   Binary,
   xSemaphoreCreateBinary,
   new_binary,
+  try_new_binary,
+  new_binary_named,
   binary,
 );
 
@@ -88,9 +127,33 @@ impl_semaphore!(
   Counting<const INITIAL: usize = 0, const MAX: usize = 4>,
   xSemaphoreCreateCounting,
   new_counting,
+  try_new_counting,
+  new_counting_named,
   counting,
 );
 
+impl Semaphore<Binary> {
+  /// Create a new dynamic binary semaphore which is initially available.
+  ///
+  /// # Panics
+  ///
+  /// Panics if [`try_new_binary`](Self::try_new_binary) fails.
+  #[cfg(freertos_feature = "dynamic_allocation")]
+  pub fn new_binary_given() -> Self {
+    let semaphore = Self::new_binary();
+    semaphore.give().unwrap();
+    semaphore
+  }
+
+  /// Create a new static binary semaphore which is initially available.
+  #[cfg(freertos_feature = "static_allocation")]
+  pub fn new_binary_given_static(semaphore: &'static mut MaybeUninit<StaticSemaphore>) -> Self {
+    let semaphore = Self::new_binary_static(semaphore);
+    semaphore.give().unwrap();
+    semaphore
+  }
+}
+
 impl<T> Deref for Semaphore<T> {
   type Target = SemaphoreHandle;
 
@@ -0,0 +1,140 @@
+//! Single-producer single-consumer channels.
+
+use core::{mem::MaybeUninit, ptr};
+
+use crate::{
+  ffi::StreamBufferHandle_t,
+  shim::{xStreamBufferCreateStatic, StaticStreamBuffer_t},
+  sync::StreamBufferHandle,
+  InterruptContext,
+  Ticks,
+};
+
+/// Storage for the bytes of a [`ByteChannel`].
+///
+/// A stream buffer needs one more byte of storage than its capacity.
+#[repr(C)]
+#[allow(unused)]
+struct Storage<const N: usize> {
+  bytes: [MaybeUninit<u8>; N],
+  extra: MaybeUninit<u8>,
+}
+
+/// A statically allocated byte channel with a capacity of `N` bytes,
+/// based on a [`StreamBuffer`](crate::sync::StreamBuffer).
+///
+/// The channel is split into a [`Producer`] and a [`Consumer`]. Since a stream buffer
+/// only supports a single writer and a single reader, neither half can be cloned
+/// and both need mutable access for sending and receiving. Both halves are [`Send`],
+/// so e.g. the producer can be moved into an interrupt handler of a UART driver
+/// while a task owns the consumer.
+///
+/// # Examples
+///
+/// ```
+/// use core::{mem::MaybeUninit, time::Duration};
+///
+/// use freertos_rust::sync::spsc::ByteChannel;
+///
+/// let (mut producer, mut consumer) = ByteChannel::split(unsafe {
+///   static mut CHANNEL: MaybeUninit<ByteChannel<16>> = MaybeUninit::uninit();
+///   &mut CHANNEL
+/// });
+///
+/// assert_eq!(producer.write(b"hello", Duration::ZERO), 5);
+///
+/// let mut buf = [0; 8];
+/// let n = consumer.read(&mut buf, Duration::ZERO);
+/// assert_eq!(&buf[..n], b"hello");
+/// ```
+pub struct ByteChannel<const N: usize> {
+  data: StaticStreamBuffer_t,
+  storage: Storage<N>,
+}
+
+impl<const N: usize> ByteChannel<N> {
+  /// Create a new byte channel and split it into its producer and consumer halves.
+  pub fn split(channel: &'static mut MaybeUninit<Self>) -> (Producer, Consumer) {
+    assert!(N > 0);
+
+    let channel_ptr = channel.as_mut_ptr();
+
+    let handle = unsafe {
+      let ptr = xStreamBufferCreateStatic(
+        N,
+        1,
+        ptr::addr_of_mut!((*channel_ptr).storage).cast(),
+        ptr::addr_of_mut!((*channel_ptr).data),
+      );
+      debug_assert!(!ptr.is_null());
+      debug_assert_eq!(ptr, ptr::addr_of!((*channel_ptr).data) as StreamBufferHandle_t);
+      StreamBufferHandle::from_ptr(ptr)
+    };
+
+    (Producer { handle }, Consumer { handle })
+  }
+}
+
+/// The writing half of a [`ByteChannel`].
+#[derive(Debug)]
+pub struct Producer {
+  handle: &'static StreamBufferHandle,
+}
+
+impl Producer {
+  /// Write bytes into the channel.
+  ///
+  /// Blocks until there is space for at least one byte or the timeout expires.
+  ///
+  /// Returns the number of bytes written, which may be less than `data.len()`.
+  #[inline]
+  pub fn write(&mut self, data: &[u8], timeout: impl Into<Ticks>) -> usize {
+    self.handle.send(data, timeout)
+  }
+
+  /// Write bytes into the channel from within an interrupt service routine.
+  ///
+  /// Returns the number of bytes written, which may be less than `data.len()`.
+  #[inline]
+  pub fn write_from_isr(&mut self, data: &[u8], ic: &InterruptContext) -> usize {
+    self.handle.send_from_isr(data, ic)
+  }
+
+  /// Get the number of bytes which can be written without blocking.
+  #[inline]
+  pub fn spaces_available(&self) -> usize {
+    self.handle.spaces_available()
+  }
+}
+
+/// The reading half of a [`ByteChannel`].
+#[derive(Debug)]
+pub struct Consumer {
+  handle: &'static StreamBufferHandle,
+}
+
+impl Consumer {
+  /// Read bytes from the channel.
+  ///
+  /// Blocks until at least one byte is available or the timeout expires.
+  ///
+  /// Returns the number of bytes read, which may be less than `buf.len()`.
+  #[inline]
+  pub fn read(&mut self, buf: &mut [u8], timeout: impl Into<Ticks>) -> usize {
+    self.handle.receive(buf, timeout)
+  }
+
+  /// Read bytes from the channel from within an interrupt service routine.
+  ///
+  /// Returns the number of bytes read, which may be less than `buf.len()`.
+  #[inline]
+  pub fn read_from_isr(&mut self, buf: &mut [u8], ic: &InterruptContext) -> usize {
+    self.handle.receive_from_isr(buf, ic)
+  }
+
+  /// Get the number of bytes which can be read without blocking.
+  #[inline]
+  pub fn bytes_available(&self) -> usize {
+    self.handle.bytes_available()
+  }
+}
@@ -0,0 +1,168 @@
+use core::{fmt, ptr};
+
+use crate::{
+  InterruptContext,
+  Ticks,
+  ffi::{Pointee, StreamBufferHandle_t},
+  shim::{
+    pdPASS,
+    pdTRUE,
+    xStreamBufferBytesAvailable,
+    xStreamBufferIsEmpty,
+    xStreamBufferIsFull,
+    xStreamBufferReceive,
+    xStreamBufferReceiveFromISR,
+    xStreamBufferReset,
+    xStreamBufferSend,
+    xStreamBufferSendFromISR,
+    xStreamBufferSetTriggerLevel,
+    xStreamBufferSpacesAvailable,
+  },
+  FreeRtosError,
+};
+
+/// A handle for managing a stream buffer.
+///
+/// See [`StreamBuffer`](crate::sync::StreamBuffer) for the preferred owned version.
+///
+/// This type is compatible with a raw FreeRTOS [`StreamBufferHandle_t`].
+#[repr(transparent)]
+pub struct StreamBufferHandle(Pointee<StreamBufferHandle_t>);
+
+impl fmt::Debug for StreamBufferHandle {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.as_ptr().fmt(f)
+  }
+}
+
+impl StreamBufferHandle {
+  /// Create a `StreamBufferHandle` from a raw handle.
+  ///
+  /// # Safety
+  ///
+  /// - `ptr` must point to a valid stream buffer.
+  /// - The stream buffer must not be deleted for the lifetime `'a` of the returned `StreamBufferHandle`.
+  #[inline]
+  pub const unsafe fn from_ptr<'a>(ptr: StreamBufferHandle_t) -> &'a Self {
+    debug_assert!(!ptr.is_null());
+    &*ptr.cast()
+  }
+
+  /// Get the raw stream buffer handle.
+  #[inline]
+  pub const fn as_ptr(&self) -> StreamBufferHandle_t {
+    ptr::addr_of!(self.0).cast_mut()
+  }
+
+  /// Send bytes to the stream buffer.
+  ///
+  /// Blocks until there is space for at least one byte or the timeout expires.
+  ///
+  /// Returns the number of bytes written, which may be less than `data.len()`.
+  #[inline]
+  pub fn send(&self, data: &[u8], timeout: impl Into<Ticks>) -> usize {
+    unsafe { xStreamBufferSend(self.as_ptr(), data.as_ptr().cast(), data.len(), timeout.into().into()) }
+  }
+
+  /// Receive bytes from the stream buffer.
+  ///
+  /// Blocks until the number of bytes in the buffer reaches the trigger level or the timeout expires.
+  ///
+  /// Returns the number of bytes read, which may be less than `buf.len()`.
+  #[inline]
+  pub fn receive(&self, buf: &mut [u8], timeout: impl Into<Ticks>) -> usize {
+    unsafe { xStreamBufferReceive(self.as_ptr(), buf.as_mut_ptr().cast(), buf.len(), timeout.into().into()) }
+  }
+
+  /// Send bytes to the stream buffer from within an interrupt service routine.
+  ///
+  /// Returns the number of bytes written, which may be less than `data.len()`.
+  #[inline]
+  pub fn send_from_isr(&self, data: &[u8], ic: &InterruptContext) -> usize {
+    unsafe { xStreamBufferSendFromISR(self.as_ptr(), data.as_ptr().cast(), data.len(), ic.as_ptr()) }
+  }
+
+  /// Receive bytes from the stream buffer from within an interrupt service routine.
+  ///
+  /// Returns the number of bytes read, which may be less than `buf.len()`.
+  #[inline]
+  pub fn receive_from_isr(&self, buf: &mut [u8], ic: &InterruptContext) -> usize {
+    unsafe { xStreamBufferReceiveFromISR(self.as_ptr(), buf.as_mut_ptr().cast(), buf.len(), ic.as_ptr()) }
+  }
+
+  /// Reset the stream buffer to its initial empty state.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Unavailable`] if a task is blocked waiting to send to
+  /// or receive from the stream buffer, in which case the stream buffer is not reset.
+  #[inline]
+  pub fn reset(&self) -> Result<(), FreeRtosError> {
+    match unsafe { xStreamBufferReset(self.as_ptr()) } {
+      pdPASS => Ok(()),
+      _ => Err(FreeRtosError::Unavailable),
+    }
+  }
+
+  /// Reset the stream buffer to its initial empty state from within an interrupt service routine.
+  ///
+  /// This requires FreeRTOS 11 or newer.
+  ///
+  /// # Errors
+  ///
+  /// See [`reset`](StreamBufferHandle::reset).
+  #[cfg(freertos_feature = "stream_buffer_reset_from_isr")]
+  #[inline]
+  pub fn reset_from_isr(&self, _ic: &InterruptContext) -> Result<(), FreeRtosError> {
+    match unsafe { crate::shim::xStreamBufferResetFromISR(self.as_ptr()) } {
+      pdPASS => Ok(()),
+      _ => Err(FreeRtosError::Unavailable),
+    }
+  }
+
+  /// Check whether the stream buffer is empty.
+  ///
+  /// This can also be called from within an interrupt service routine.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    unsafe { xStreamBufferIsEmpty(self.as_ptr()) == pdTRUE }
+  }
+
+  /// Check whether the stream buffer is full.
+  ///
+  /// This can also be called from within an interrupt service routine.
+  #[inline]
+  pub fn is_full(&self) -> bool {
+    unsafe { xStreamBufferIsFull(self.as_ptr()) == pdTRUE }
+  }
+
+  /// Get the number of bytes which can be read from the stream buffer.
+  ///
+  /// This can also be called from within an interrupt service routine.
+  #[inline]
+  pub fn bytes_available(&self) -> usize {
+    unsafe { xStreamBufferBytesAvailable(self.as_ptr()) }
+  }
+
+  /// Get the number of bytes which can be written to the stream buffer.
+  ///
+  /// This can also be called from within an interrupt service routine.
+  #[inline]
+  pub fn spaces_available(&self) -> usize {
+    unsafe { xStreamBufferSpacesAvailable(self.as_ptr()) }
+  }
+
+  /// Set the number of bytes which must be in the stream buffer
+  /// before a task blocked in [`receive`](StreamBufferHandle::receive) is woken.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if `trigger_level` is larger than the buffer's capacity.
+  #[inline]
+  pub fn set_trigger_level(&self, trigger_level: usize) -> Result<(), FreeRtosError> {
+    match unsafe { xStreamBufferSetTriggerLevel(self.as_ptr(), trigger_level) } {
+      pdPASS => Ok(()),
+      _ => Err(FreeRtosError::InvalidParameter),
+    }
+  }
+}
@@ -0,0 +1,70 @@
+use core::ops::Deref;
+
+mod handle;
+pub use handle::StreamBufferHandle;
+
+use crate::{
+  ffi::StreamBufferHandle_t,
+  shim::vStreamBufferDelete,
+};
+#[cfg(freertos_feature = "dynamic_allocation")]
+use crate::shim::xStreamBufferCreate;
+
+/// A stream buffer for sending a continuous stream of bytes from a single writer to a single reader.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+///
+/// use freertos_rust::sync::StreamBuffer;
+///
+/// let stream_buffer = StreamBuffer::<64>::new();
+/// assert_eq!(stream_buffer.send(b"hello", Duration::MAX), 5);
+///
+/// let mut buf = [0; 8];
+/// let n = stream_buffer.receive(&mut buf, Duration::ZERO);
+/// assert_eq!(&buf[..n], b"hello");
+/// ```
+pub struct StreamBuffer<const CAPACITY: usize> {
+  handle: StreamBufferHandle_t,
+}
+
+unsafe impl<const CAPACITY: usize> Send for StreamBuffer<CAPACITY> {}
+unsafe impl<const CAPACITY: usize> Sync for StreamBuffer<CAPACITY> {}
+
+#[cfg(freertos_feature = "dynamic_allocation")]
+impl<const CAPACITY: usize> StreamBuffer<CAPACITY> {
+  /// Create a new dynamic stream buffer with a trigger level of one byte.
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    Self::with_trigger_level(1)
+  }
+
+  /// Create a new dynamic stream buffer with the given trigger level.
+  ///
+  /// See [`StreamBufferHandle::set_trigger_level`].
+  pub fn with_trigger_level(trigger_level: usize) -> Self {
+    assert!(CAPACITY > 0);
+    assert!(trigger_level <= CAPACITY);
+
+    let ptr = unsafe { xStreamBufferCreate(CAPACITY, trigger_level) };
+    assert!(!ptr.is_null());
+
+    Self { handle: ptr }
+  }
+}
+
+impl<const CAPACITY: usize> Deref for StreamBuffer<CAPACITY> {
+  type Target = StreamBufferHandle;
+
+  fn deref(&self) -> &Self::Target {
+    unsafe { StreamBufferHandle::from_ptr(self.handle) }
+  }
+}
+
+impl<const CAPACITY: usize> Drop for StreamBuffer<CAPACITY> {
+  fn drop(&mut self) {
+    unsafe { vStreamBufferDelete(self.handle) }
+  }
+}
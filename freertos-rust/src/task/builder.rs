@@ -8,9 +8,9 @@ use crate::{
   shim::{vTaskDelete, pdPASS},
 };
 #[cfg(freertos_feature = "dynamic_allocation")]
-use crate::shim::xTaskCreate;
+use crate::FreeRtosError;
 #[cfg(freertos_feature = "static_allocation")]
-use crate::{StaticTask, shim::xTaskCreateStatic};
+use crate::{StaticTask, shim::{StackType_t, StaticTask_t}};
 
 use super::{Task, TaskPriority, TaskName, MINIMAL_STACK_SIZE};
 
@@ -22,6 +22,10 @@ pub struct TaskBuilder<'n> {
   name: &'n str,
   stack_size: usize,
   priority: TaskPriority,
+  #[cfg(freertos_feature = "trace_facility")]
+  stack_canary: bool,
+  #[cfg(all(freertos_feature = "smp", freertos_feature = "core_affinity"))]
+  core_affinity: Option<u32>,
 }
 
 impl TaskBuilder<'_> {
@@ -30,6 +34,10 @@ impl TaskBuilder<'_> {
       name: "",
       stack_size: MINIMAL_STACK_SIZE,
       priority: TaskPriority::new(1).unwrap(),
+      #[cfg(freertos_feature = "trace_facility")]
+      stack_canary: false,
+      #[cfg(all(freertos_feature = "smp", freertos_feature = "core_affinity"))]
+      core_affinity: None,
     }
   }
 }
@@ -41,10 +49,17 @@ impl TaskBuilder<'_> {
       name,
       stack_size: self.stack_size,
       priority: self.priority,
+      #[cfg(freertos_feature = "trace_facility")]
+      stack_canary: self.stack_canary,
+      #[cfg(all(freertos_feature = "smp", freertos_feature = "core_affinity"))]
+      core_affinity: self.core_affinity,
     }
   }
 
   /// Set the stack size in words.
+  ///
+  /// The stack size must be at least [`MINIMAL_STACK_SIZE`] and fit into `configSTACK_DEPTH_TYPE`,
+  /// otherwise creating the task fails.
   pub const fn stack_size(mut self, stack_size: usize) -> Self {
     self.stack_size = stack_size;
     self
@@ -56,9 +71,98 @@ impl TaskBuilder<'_> {
     self
   }
 
+  /// Reserve a software stack canary.
+  ///
+  /// FreeRTOS fills the stack of every new task with [`STACK_CANARY`](crate::task::STACK_CANARY).
+  /// The last [`STACK_CANARY_LEN`](crate::task::STACK_CANARY_LEN) words of this fill act as the
+  /// canary, which can be checked using [`TaskHandle::check_canary`](crate::task::TaskHandle::check_canary).
+  /// This allows detecting stack overflows on ports where `configCHECK_FOR_STACK_OVERFLOW` is not
+  /// available. Since the canary is not written separately, it does not interfere with the stack
+  /// overflow check of FreeRTOS or with [`TaskHandle::stack_high_water_mark`](crate::task::TaskHandle::stack_high_water_mark).
+  ///
+  /// For dynamically allocated tasks, the stack is enlarged by the size of the canary. For
+  /// statically allocated tasks, the stack is given, so the canary reduces the usable stack size.
+  ///
+  /// This requires `configUSE_TRACE_FACILITY` and is only supported on ports where the stack grows downwards.
+  #[cfg(freertos_feature = "trace_facility")]
+  pub const fn stack_canary(mut self, stack_canary: bool) -> Self {
+    self.stack_canary = stack_canary;
+    self
+  }
+
+  /// Set the cores this task is allowed to run on.
+  ///
+  /// Bit `n` of `mask` corresponds to core `n`. The task is created with this affinity
+  /// using `xTaskCreateAffinitySet` or `xTaskCreateStaticAffinitySet`, so it never
+  /// runs on a different core.
+  ///
+  /// This requires `configNUMBER_OF_CORES > 1` and `configUSE_CORE_AFFINITY`.
+  #[cfg(all(freertos_feature = "smp", freertos_feature = "core_affinity"))]
+  pub const fn core_affinity(mut self, mask: u32) -> Self {
+    self.core_affinity = Some(mask);
+    self
+  }
+
+  /// Convert `stack_size` into the stack depth type of the port.
+  ///
+  /// Returns `None` if `stack_size` is smaller than [`MINIMAL_STACK_SIZE`]
+  /// or does not fit into `configSTACK_DEPTH_TYPE`.
+  fn stack_depth<D: TryFrom<usize>>(stack_size: usize) -> Option<D> {
+    if stack_size < MINIMAL_STACK_SIZE {
+      return None
+    }
+
+    stack_size.try_into().ok()
+  }
+
+  /// Get the stack size of a dynamically allocated task, including the canary, if any.
+  #[cfg(freertos_feature = "dynamic_allocation")]
+  fn dynamic_stack_size(&self) -> usize {
+    #[cfg(freertos_feature = "trace_facility")]
+    if self.stack_canary {
+      return self.stack_size.saturating_add(super::STACK_CANARY_LEN)
+    }
+
+    self.stack_size
+  }
+
+  /// Get the core affinity mask to create the task with.
+  #[cfg(all(freertos_feature = "smp", freertos_feature = "core_affinity"))]
+  fn core_affinity_mask(&self) -> crate::shim::UBaseType_t {
+    // No affinity, i.e. `tskNO_AFFINITY`, if none was set.
+    self.core_affinity.map_or(!0, |mask| mask as _)
+  }
+
   /// Create the [`Task`].
+  ///
+  /// # Panics
+  ///
+  /// This panics if the stack size is invalid or the task could not be allocated,
+  /// see [`try_create`](TaskBuilder::try_create) for a non-panicking version.
   #[cfg(freertos_feature = "dynamic_allocation")]
   pub fn create<F>(&self, f: F) -> Task
+  where
+    F: FnOnce(&mut CurrentTask) + Send + 'static,
+  {
+    match self.try_create(f) {
+      Ok(task) => task,
+      Err(FreeRtosError::InvalidParameter) => panic!(
+        "invalid stack size {}, must be at least {} words and fit into `configSTACK_DEPTH_TYPE`",
+        self.stack_size, MINIMAL_STACK_SIZE,
+      ),
+      Err(err) => panic!("failed to create task: {err}"),
+    }
+  }
+
+  /// Try creating the [`Task`].
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if the stack size is smaller than
+  /// [`MINIMAL_STACK_SIZE`] or does not fit into `configSTACK_DEPTH_TYPE` and
+  /// [`FreeRtosError::OutOfMemory`] if the task could not be allocated.
+  #[cfg(freertos_feature = "dynamic_allocation")]
+  pub fn try_create<F>(&self, f: F) -> Result<Task, FreeRtosError>
   where
     F: FnOnce(&mut CurrentTask) + Send + 'static,
   {
@@ -77,6 +181,8 @@ impl TaskBuilder<'_> {
       }
     }
 
+    let stack_depth = Self::stack_depth(self.dynamic_stack_size()).ok_or(FreeRtosError::InvalidParameter)?;
+
     let name = TaskName::new(self.name);
 
     let function: BoxTaskFn = Box::new(f);
@@ -84,10 +190,21 @@ impl TaskBuilder<'_> {
     let mut ptr = ptr::null_mut();
 
     unsafe {
-      let res = xTaskCreate(
+      #[cfg(all(freertos_feature = "smp", freertos_feature = "core_affinity"))]
+      let res = crate::shim::xTaskCreateAffinitySet(
+        Some(task_function),
+        name.as_ptr(),
+        stack_depth,
+        function_ptr.cast(),
+        self.priority.to_freertos(),
+        self.core_affinity_mask(),
+        &mut ptr,
+      );
+      #[cfg(not(all(freertos_feature = "smp", freertos_feature = "core_affinity")))]
+      let res = crate::shim::xTaskCreate(
         Some(task_function),
         name.as_ptr(),
-        self.stack_size.try_into().unwrap_or(!0),
+        stack_depth,
         function_ptr.cast(),
         self.priority.to_freertos(),
         &mut ptr,
@@ -96,22 +213,29 @@ impl TaskBuilder<'_> {
       if res == pdPASS {
         debug_assert!(!ptr.is_null());
 
-        Task {
+        Ok(Task {
           handle: ptr,
           function: Some(Box::from_raw(function_ptr)),
-        }
+        })
       } else {
         drop(Box::from_raw(function_ptr));
-        assert_eq!(res, pdPASS);
-        unreachable!();
+        Err(FreeRtosError::OutOfMemory)
       }
     }
   }
 
   /// Create the static [`Task`].
   ///
+  /// The stack size is determined by `STACK_SIZE`, the stack size set using
+  /// [`stack_size`](TaskBuilder::stack_size) is ignored.
+  ///
   /// The returned task needs to be started.
   ///
+  /// # Panics
+  ///
+  /// This panics if `STACK_SIZE` is smaller than [`MINIMAL_STACK_SIZE`] or does
+  /// not fit into `configSTACK_DEPTH_TYPE`.
+  ///
   /// # Safety
   ///
   /// The returned task must have a `'static` lifetime.
@@ -127,19 +251,124 @@ impl TaskBuilder<'_> {
   ///   // ...
   /// }
   ///
-  /// static mut TASK: MaybeUninit<StaticTask<128>> = MaybeUninit::uninit();  ///
+  /// static mut TASK: MaybeUninit<StaticTask> = MaybeUninit::uninit();
+  ///
   /// let _task = Task::new().name("my_task").create_static(unsafe { &mut TASK }, my_task);
   /// ```
   #[cfg(freertos_feature = "static_allocation")]
   pub fn create_static<const STACK_SIZE: usize>(self, task: &'static mut MaybeUninit<StaticTask<STACK_SIZE>>, f: fn(&mut CurrentTask)) -> Task {
-    assert!(STACK_SIZE <= self.stack_size);
+    let task_ptr = task.as_mut_ptr();
 
-    extern "C" fn task_function(param: *mut c_void) {
+    unsafe {
+      let stack_buffer = ptr::addr_of_mut!((*task_ptr).stack).cast();
+      let task_buffer = ptr::addr_of_mut!((*task_ptr).data);
+
+      self.create_static_raw(stack_buffer, STACK_SIZE, task_buffer, f)
+    }
+  }
+
+  /// Create the static [`Task`] using the given stack and task buffers.
+  ///
+  /// Unlike [`create_static`](TaskBuilder::create_static), the stack size is determined at runtime
+  /// by the length of `stack`, so stacks can be taken from a user-managed pool. The stack size set
+  /// using [`stack_size`](TaskBuilder::stack_size) is ignored.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `stack` is shorter than [`MINIMAL_STACK_SIZE`] or its length does
+  /// not fit into `configSTACK_DEPTH_TYPE`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use core::mem::MaybeUninit;
+  ///
+  /// use freertos_rust::{Task, CurrentTask, MINIMAL_STACK_SIZE, ffi::StackType_t, ffi::StaticTask_t};
+  ///
+  /// fn my_task(task: &mut CurrentTask) {
+  ///   // ...
+  /// }
+  ///
+  /// static mut STACK: [MaybeUninit<StackType_t>; MINIMAL_STACK_SIZE * 2] = [MaybeUninit::uninit(); MINIMAL_STACK_SIZE * 2];
+  /// static mut TCB: MaybeUninit<StaticTask_t> = MaybeUninit::uninit();
+  ///
+  /// let stack_size = MINIMAL_STACK_SIZE + MINIMAL_STACK_SIZE / 2;
+  /// let _task = Task::new().name("my_task").create_with_stack(
+  ///   unsafe { &mut STACK[..stack_size] },
+  ///   unsafe { &mut TCB },
+  ///   my_task,
+  /// );
+  /// ```
+  #[cfg(freertos_feature = "static_allocation")]
+  pub fn create_with_stack(
+    self,
+    stack: &'static mut [MaybeUninit<StackType_t>],
+    task: &'static mut MaybeUninit<StaticTask_t>,
+    f: fn(&mut CurrentTask),
+  ) -> Task {
+    unsafe { self.create_static_raw(stack.as_mut_ptr().cast(), stack.len(), task.as_mut_ptr(), f) }
+  }
+
+  /// Create the static [`Task`] with a closure, which is stored in `task` alongside the stack.
+  ///
+  /// Unlike [`create_static`](TaskBuilder::create_static), this allows the task function to
+  /// capture its environment without allocating. The stack size is determined by `STACK_SIZE`,
+  /// the stack size set using [`stack_size`](TaskBuilder::stack_size) is ignored.
+  ///
+  /// If the returned task is deleted before it started running, the closure is never dropped.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `STACK_SIZE` is smaller than [`MINIMAL_STACK_SIZE`] or does
+  /// not fit into `configSTACK_DEPTH_TYPE`.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// extern crate alloc;
+  /// use alloc::boxed::Box;
+  /// use core::{mem::MaybeUninit, time::Duration};
+  ///
+  /// use freertos_rust::{Task, StaticTask, CurrentTask};
+  ///
+  /// struct Led { on: bool }
+  ///
+  /// impl Led {
+  ///   fn toggle(&mut self) {
+  ///     self.on = !self.on;
+  ///   }
+  /// }
+  ///
+  /// let mut led = Led { on: false };
+  /// let blink = move |task: &mut CurrentTask| loop {
+  ///   led.toggle();
+  ///   task.delay(Duration::from_millis(500));
+  /// };
+  ///
+  /// // Closure types cannot be named in a `static`, so the slot is allocated once
+  /// // during startup here. Without an allocator, a helper like `static_cell::StaticCell`
+  /// // can provide the slot instead.
+  /// fn task_slot<F: 'static>(_: &F) -> &'static mut MaybeUninit<StaticTask<128, F>> {
+  ///   Box::leak(Box::new(MaybeUninit::uninit()))
+  /// }
+  ///
+  /// let _task = Task::new().name("blink").create_static_with(task_slot(&blink), blink);
+  /// ```
+  #[cfg(freertos_feature = "static_allocation")]
+  pub fn create_static_with<const STACK_SIZE: usize, F>(
+    self,
+    task: &'static mut MaybeUninit<StaticTask<STACK_SIZE, F>>,
+    f: F,
+  ) -> Task
+  where
+    F: FnOnce(&mut CurrentTask) + Send + 'static,
+  {
+    extern "C" fn task_function<F: FnOnce(&mut CurrentTask)>(param: *mut c_void) {
       unsafe {
         // NOTE: New scope so that everything is dropped before the task is deleted.
         {
           let mut current_task = CurrentTask::new_unchecked();
-          let function: fn(&mut CurrentTask) = mem::transmute(param);
+          let function: F = ptr::read(param.cast());
           function(&mut current_task);
         }
 
@@ -148,34 +377,89 @@ impl TaskBuilder<'_> {
       }
     }
 
-    let name = TaskName::new(self.name);
-
-    let function_ptr = f as *mut c_void;
-
     let task_ptr = task.as_mut_ptr();
 
     unsafe {
+      let function_ptr = ptr::addr_of_mut!((*task_ptr).function).cast::<F>();
+      function_ptr.write(f);
+
       let stack_buffer = ptr::addr_of_mut!((*task_ptr).stack).cast();
       let task_buffer = ptr::addr_of_mut!((*task_ptr).data);
 
-      let ptr = xTaskCreateStatic(
-        Some(task_function),
-        name.as_ptr(),
-        self.stack_size as _,
-        function_ptr,
-        self.priority.to_freertos(),
-        stack_buffer,
-        task_buffer,
-      );
+      self.create_static_with_param(stack_buffer, STACK_SIZE, task_buffer, task_function::<F>, function_ptr.cast())
+    }
+  }
 
-      debug_assert!(!ptr.is_null());
-      debug_assert_eq!(ptr, task_buffer.cast());
+  #[cfg(freertos_feature = "static_allocation")]
+  unsafe fn create_static_raw(
+    &self,
+    stack_buffer: *mut StackType_t,
+    stack_size: usize,
+    task_buffer: *mut StaticTask_t,
+    f: fn(&mut CurrentTask),
+  ) -> Task {
+    extern "C" fn task_function(param: *mut c_void) {
+      unsafe {
+        // NOTE: New scope so that everything is dropped before the task is deleted.
+        {
+          let mut current_task = CurrentTask::new_unchecked();
+          let function: fn(&mut CurrentTask) = mem::transmute(param);
+          function(&mut current_task);
+        }
 
-      Task {
-        handle: ptr,
-        #[cfg(freertos_feature = "dynamic_allocation")]
-        function: None,
+        vTaskDelete(ptr::null_mut());
+        unreachable!();
       }
     }
+
+    self.create_static_with_param(stack_buffer, stack_size, task_buffer, task_function, f as *mut c_void)
+  }
+
+  #[cfg(freertos_feature = "static_allocation")]
+  unsafe fn create_static_with_param(
+    &self,
+    stack_buffer: *mut StackType_t,
+    stack_size: usize,
+    task_buffer: *mut StaticTask_t,
+    task_function: extern "C" fn(*mut c_void),
+    param: *mut c_void,
+  ) -> Task {
+    let stack_depth = Self::stack_depth(stack_size).unwrap_or_else(|| panic!(
+      "invalid stack size {}, must be at least {} words and fit into `configSTACK_DEPTH_TYPE`",
+      stack_size, MINIMAL_STACK_SIZE,
+    ));
+
+    let name = TaskName::new(self.name);
+
+    #[cfg(all(freertos_feature = "smp", freertos_feature = "core_affinity"))]
+    let ptr = crate::shim::xTaskCreateStaticAffinitySet(
+      Some(task_function),
+      name.as_ptr(),
+      stack_depth,
+      param,
+      self.priority.to_freertos(),
+      stack_buffer,
+      task_buffer,
+      self.core_affinity_mask(),
+    );
+    #[cfg(not(all(freertos_feature = "smp", freertos_feature = "core_affinity")))]
+    let ptr = crate::shim::xTaskCreateStatic(
+      Some(task_function),
+      name.as_ptr(),
+      stack_depth,
+      param,
+      self.priority.to_freertos(),
+      stack_buffer,
+      task_buffer,
+    );
+
+    debug_assert!(!ptr.is_null());
+    debug_assert_eq!(ptr, task_buffer.cast());
+
+    Task {
+      handle: ptr,
+      #[cfg(freertos_feature = "dynamic_allocation")]
+      function: None,
+    }
   }
 }
@@ -4,13 +4,18 @@ use crate::{TaskHandle, FreeRtosError};
 use crate::ffi::TickType_t;
 use crate::shim::{
   vTaskDelay,
-  vTaskDelayUntil,
   portTICK_PERIOD_MS,
   pdTRUE,
   pdFALSE,
   ulTaskNotifyTake, xTaskNotifyWait, xTaskGetCurrentTaskHandle, pdPASS,
+  portMAX_DELAY,
+  configTASK_NOTIFICATION_ARRAY_ENTRIES,
+  freertos_rs_task_notify_wait_indexed,
+  freertos_rs_task_notify_take_indexed,
+  freertos_rs_enter_critical,
+  freertos_rs_exit_critical,
 };
-use crate::task::Scheduler;
+use crate::task::{Scheduler, TaskPriority};
 use crate::ticks::Ticks;
 
 /// Delay the current task by the given duration, minus the
@@ -66,6 +71,114 @@ impl CurrentTask {
     }
   }
 
+  /// Take a notification with the given index and either clear the notification value or decrement it by one.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `index` is not within \[0, `configTASK_NOTIFICATION_ARRAY_ENTRIES`\).
+  pub fn take_notification_indexed(&mut self, index: usize, clear: bool, timeout: impl Into<Ticks>) -> u32 {
+    assert!(index < configTASK_NOTIFICATION_ARRAY_ENTRIES as _);
+
+    unsafe {
+      freertos_rs_task_notify_take_indexed(index as _, if clear { pdTRUE } else { pdFALSE }, timeout.into().into())
+    }
+  }
+
+  /// Wait for a notification with the given index.
+  ///
+  /// Clears the bits set in `clear_on_entry` after entering and clears the
+  /// bits set in `clear_on_exit` before returning from the function.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `index` is not within \[0, `configTASK_NOTIFICATION_ARRAY_ENTRIES`\).
+  pub fn wait_for_notification_indexed(
+    &mut self,
+    index: usize,
+    clear_on_entry: u32,
+    clear_on_exit: u32,
+    timeout: impl Into<Ticks>,
+  ) -> Result<u32, FreeRtosError> {
+    assert!(index < configTASK_NOTIFICATION_ARRAY_ENTRIES as _);
+
+    Self::notify_wait_indexed(index, clear_on_entry, clear_on_exit, timeout.into())
+  }
+
+  fn notify_wait_indexed(
+    index: usize,
+    clear_on_entry: u32,
+    clear_on_exit: u32,
+    timeout: Ticks,
+  ) -> Result<u32, FreeRtosError> {
+    let mut val = 0;
+    match unsafe {
+      freertos_rs_task_notify_wait_indexed(
+        index as _,
+        clear_on_entry,
+        clear_on_exit,
+        &mut val as *mut _,
+        timeout.into(),
+      )
+    } {
+      pdPASS => Ok(val),
+      _ => Err(FreeRtosError::Timeout),
+    }
+  }
+
+  /// Wait for a notification on any of the given `indices`.
+  ///
+  /// FreeRTOS cannot block on multiple notification indices at once. Therefore,
+  /// all `indices` are first checked without blocking. If none of them has a
+  /// pending notification, the task blocks on `aggregate_index` and checks
+  /// all `indices` again once it is notified or the timeout expires. Senders must
+  /// therefore also notify `aggregate_index`, e.g. with [`TaskNotification::NoAction`](crate::task::TaskNotification::NoAction),
+  /// after notifying one of the `indices`.
+  ///
+  /// The aggregate index should be reserved for this purpose. In particular, it should not
+  /// be index `0`, which is used by all non-indexed notification functions, e.g.
+  /// [`TaskHandle::notify`] or [`Condvar`](crate::sync::Condvar).
+  ///
+  /// Returns the first index in `indices` with a pending notification together with its
+  /// notification value. The notification value of the returned index is cleared.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `aggregate_index` or any of the `indices` is not within
+  /// \[0, `configTASK_NOTIFICATION_ARRAY_ENTRIES`\) or if `indices` contains `aggregate_index`.
+  pub fn wait_any_index(
+    &mut self,
+    aggregate_index: usize,
+    indices: &[usize],
+    timeout: impl Into<Ticks>,
+  ) -> Result<(usize, u32), FreeRtosError> {
+    assert!(aggregate_index < configTASK_NOTIFICATION_ARRAY_ENTRIES as _);
+
+    for &index in indices {
+      assert!(index < configTASK_NOTIFICATION_ARRAY_ENTRIES as _);
+      assert_ne!(index, aggregate_index, "`indices` must not contain the aggregate index");
+    }
+
+    let timeout = timeout.into();
+    let start = Scheduler::tick_count();
+
+    loop {
+      for &index in indices {
+        if let Ok(value) = Self::notify_wait_indexed(index, 0, !0, Ticks::new(0)) {
+          return Ok((index, value))
+        }
+      }
+
+      let remaining = if timeout.ticks == portMAX_DELAY {
+        timeout
+      } else {
+        let elapsed = Scheduler::tick_count().wrapping_sub(start);
+        Ticks::new(timeout.ticks.saturating_sub(elapsed.ticks))
+      };
+
+      Self::notify_wait_indexed(aggregate_index, 0, 0, remaining)?;
+    }
+  }
+
   /// Clear pending notifications for this task.
   ///
   /// Returns whether a pending notification was cleared.
@@ -73,22 +186,111 @@ impl CurrentTask {
     self.handle.clear_notification()
   }
 
+  /// Get the handle of this task.
+  #[inline]
+  pub fn handle(&self) -> &TaskHandle {
+    self.handle
+  }
+
   /// Get the name of this task.
   pub fn name(&self) -> &str {
     self.handle.name()
   }
 
+  /// Get the priority of this task.
+  pub fn priority(&self) -> TaskPriority {
+    self.handle.priority()
+  }
+
+  /// Set the priority of this task.
+  ///
+  /// If the priority is lowered below that of another ready task, `vTaskPrioritySet`
+  /// already yields, so that task is scheduled immediately.
+  pub fn set_priority(&mut self, priority: TaskPriority) {
+    self.handle.set_priority(priority);
+  }
+
+  /// Run `f` inside a critical section.
+  ///
+  /// Interrupts with a priority up to `configMAX_SYSCALL_INTERRUPT_PRIORITY` are masked
+  /// while `f` runs. If a FreeRTOS API call inside `f` makes a higher priority task ready,
+  /// the context switch it requests is pended and performed as soon as the critical
+  /// section is exited, so the task only yields if appropriate.
+  pub fn with_critical<R>(&mut self, f: impl FnOnce() -> R) -> R {
+    unsafe {
+      freertos_rs_enter_critical();
+      let res = f();
+      freertos_rs_exit_critical();
+      res
+    }
+  }
+
   /// Delay the execution of the current task.
+  ///
+  /// This does not advance the wake time used by [`delay_until`](CurrentTask::delay_until).
+  /// Call [`reset_wake_time`](CurrentTask::reset_wake_time) after this if the task
+  /// continues with periodic delays.
   pub fn delay(&mut self, delay: impl Into<Ticks>) {
     unsafe { vTaskDelay(delay.into().into()) }
   }
 
   /// Delay the execution of the current task by the given duration,
   /// minus the time spent in this task since the last delay.
-  pub fn delay_until(&mut self, delay: impl Into<Ticks>) {
-    unsafe {
-      vTaskDelayUntil(&mut self.last_wake_time, delay.into().into())
+  ///
+  /// Returns whether the task was actually delayed. If the next wake time is
+  /// already in the past, i.e. the loop overran, this returns `false` immediately.
+  ///
+  /// On FreeRTOS versions without `xTaskDelayUntil`, this always returns `true`.
+  ///
+  /// The wake time starts at the tick count at which the task was started and is only
+  /// advanced by this function. If the task was blocked in the meantime, e.g. using
+  /// [`delay`](CurrentTask::delay), the wake time lags behind and the following calls
+  /// return immediately until it has caught up. Use [`reset_wake_time`](CurrentTask::reset_wake_time)
+  /// to avoid this.
+  pub fn delay_until(&mut self, delay: impl Into<Ticks>) -> bool {
+    #[cfg(freertos_feature = "task_delay_until")]
+    {
+      unsafe {
+        crate::shim::xTaskDelayUntil(&mut self.last_wake_time, delay.into().into()) == pdTRUE
+      }
     }
+
+    #[cfg(not(freertos_feature = "task_delay_until"))]
+    {
+      unsafe {
+        crate::shim::vTaskDelayUntil(&mut self.last_wake_time, delay.into().into())
+      }
+
+      true
+    }
+  }
+
+  /// Reset the wake time used by [`delay_until`](CurrentTask::delay_until) to the current tick count.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::task::Task;
+  ///
+  /// let _task = Task::new().create(|task| {
+  ///   // Wait for the system to settle.
+  ///   task.delay(Duration::from_secs(1));
+  ///
+  ///   // Without this, the first calls to `delay_until` would return
+  ///   // immediately to make up for the delay above.
+  ///   task.reset_wake_time();
+  ///
+  ///   loop {
+  ///     task.delay_until(Duration::from_millis(10));
+  ///     // ...
+  ///   }
+  /// });
+  /// ```
+  #[inline]
+  pub fn reset_wake_time(&mut self) {
+    self.last_wake_time = Scheduler::tick_count().into();
   }
 }
 
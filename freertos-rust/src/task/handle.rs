@@ -1,4 +1,6 @@
 use core::{ffi::CStr, str, fmt, ptr};
+#[cfg(freertos_feature = "trace_facility")]
+use core::{mem::MaybeUninit, slice};
 
 use crate::FreeRtosError;
 use crate::InterruptContext;
@@ -13,9 +15,19 @@ use crate::task::TaskNotification;
 use crate::ffi::TaskHandle_t;
 use crate::shim::freertos_rs_task_notify_indexed;
 use crate::shim::freertos_rs_task_notify_indexed_from_isr;
+use crate::shim::freertos_rs_task_notify_give_indexed;
+use crate::shim::{freertos_rs_task_notify_and_query, freertos_rs_task_notify_and_query_from_isr};
 use crate::shim::pdPASS;
 use crate::shim::xTaskNotify;
 use crate::shim::{uxTaskGetTaskNumber, vTaskSetTaskNumber};
+#[cfg(freertos_feature = "trace_facility")]
+use crate::shim::{vTaskGetInfo, pdFALSE, eTaskState_eInvalid, portSTACK_GROWTH, StackType_t, TaskStatus_t};
+use crate::shim::{uxTaskPriorityGet, vTaskPrioritySet};
+use crate::shim::{eTaskGetState, portMAX_DELAY, vTaskDelay};
+use crate::task::{Scheduler, TaskPriority, TaskState};
+#[cfg(freertos_feature = "trace_facility")]
+use crate::task::{STACK_CANARY, STACK_CANARY_LEN};
+use crate::Ticks;
 
 /// A handle for managing a task.
 ///
@@ -76,6 +88,160 @@ impl TaskHandle {
     }
   }
 
+  /// Get the priority of this task.
+  #[inline]
+  pub fn priority(&self) -> TaskPriority {
+    unsafe { TaskPriority::new_unchecked(uxTaskPriorityGet(self.as_ptr()) as u8) }
+  }
+
+  /// Set the priority of this task.
+  ///
+  /// A context switch occurs before returning if the priority is raised above
+  /// that of the calling task.
+  #[inline]
+  pub fn set_priority(&self, priority: TaskPriority) {
+    unsafe { vTaskPrioritySet(self.as_ptr(), priority.to_freertos()) }
+  }
+
+  /// Get the current state of this task.
+  ///
+  /// Unlike [`SystemState`](crate::task::SystemState), this only queries a single
+  /// task and therefore does not allocate.
+  ///
+  /// This requires `INCLUDE_eTaskGetState`.
+  #[inline]
+  pub fn state(&self) -> TaskState {
+    TaskState::from(unsafe { eTaskGetState(self.as_ptr()) } as u32)
+  }
+
+  /// Get the cores this task is allowed to run on.
+  ///
+  /// Bit `n` of the returned mask corresponds to core `n`.
+  ///
+  /// This requires `configNUMBER_OF_CORES > 1` and `configUSE_CORE_AFFINITY`.
+  #[cfg(all(freertos_feature = "smp", freertos_feature = "core_affinity"))]
+  #[inline]
+  pub fn core_affinity(&self) -> u32 {
+    unsafe { crate::shim::vTaskCoreAffinityGet(self.as_ptr()) as u32 }
+  }
+
+  /// Set the cores this task is allowed to run on.
+  ///
+  /// Bit `n` of `mask` corresponds to core `n`.
+  ///
+  /// This requires `configNUMBER_OF_CORES > 1` and `configUSE_CORE_AFFINITY`.
+  #[cfg(all(freertos_feature = "smp", freertos_feature = "core_affinity"))]
+  #[inline]
+  pub fn set_core_affinity(&self, mask: u32) {
+    unsafe { crate::shim::vTaskCoreAffinitySet(self.as_ptr(), mask as _) }
+  }
+
+  /// Wait until this task reaches the `target` state.
+  ///
+  /// FreeRTOS has no notification for task state changes, so the state is polled,
+  /// delaying the calling task for `poll_interval` between each check. This is
+  /// inherently racy: The task may pass through the `target` state between two
+  /// checks without being noticed, and may have left it again by the time this
+  /// function returns. It is therefore best suited for states which a task only
+  /// leaves on request, e.g. [`TaskState::Suspended`].
+  ///
+  /// This must not be called from the task itself.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::Timeout`] if the task did not reach the `target` state before the timeout expired.
+  pub fn wait_until_state(
+    &self,
+    target: TaskState,
+    poll_interval: impl Into<Ticks>,
+    timeout: impl Into<Ticks>,
+  ) -> Result<(), FreeRtosError> {
+    let poll_interval = poll_interval.into().ticks.max(1);
+    let timeout = timeout.into();
+    let start = Scheduler::tick_count();
+
+    loop {
+      if self.state() == target {
+        return Ok(())
+      }
+
+      let remaining = if timeout.ticks == portMAX_DELAY {
+        poll_interval
+      } else {
+        let elapsed = Scheduler::tick_count().wrapping_sub(start);
+        let remaining = timeout.ticks.saturating_sub(elapsed.ticks);
+
+        if remaining == 0 {
+          return Err(FreeRtosError::Timeout)
+        }
+
+        remaining.min(poll_interval)
+      };
+
+      unsafe { vTaskDelay(remaining) };
+    }
+  }
+
+  /// Set the local storage pointer at `index` for this task.
+  ///
+  /// This requires `configNUM_THREAD_LOCAL_STORAGE_POINTERS` to be greater than `0`.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `index` is not within \[0, `configNUM_THREAD_LOCAL_STORAGE_POINTERS`\).
+  #[cfg(freertos_feature = "local_storage")]
+  #[inline]
+  pub fn set_local_storage(&self, index: usize, ptr: *mut core::ffi::c_void) {
+    assert!(index < crate::shim::configNUM_THREAD_LOCAL_STORAGE_POINTERS as _);
+    unsafe { crate::shim::vTaskSetThreadLocalStoragePointer(self.as_ptr(), index as _, ptr) }
+  }
+
+  /// Get the local storage pointer at `index` for this task.
+  ///
+  /// This requires `configNUM_THREAD_LOCAL_STORAGE_POINTERS` to be greater than `0`.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `index` is not within \[0, `configNUM_THREAD_LOCAL_STORAGE_POINTERS`\).
+  #[cfg(freertos_feature = "local_storage")]
+  #[inline]
+  pub fn local_storage(&self, index: usize) -> *mut core::ffi::c_void {
+    assert!(index < crate::shim::configNUM_THREAD_LOCAL_STORAGE_POINTERS as _);
+    unsafe { crate::shim::pvTaskGetThreadLocalStoragePointer(self.as_ptr(), index as _) }
+  }
+
+  /// Abort a blocking delay of this task.
+  ///
+  /// This wakes the task if it is blocked in a delay or waiting on a queue, semaphore
+  /// or notification with a timeout, in which case the blocking call times out.
+  ///
+  /// Returns whether the task was in the blocked state.
+  ///
+  /// This requires `INCLUDE_xTaskAbortDelay`.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::task::Task;
+  ///
+  /// let sleeper = Task::new().name("sleeper").create(|task| {
+  ///   task.delay(Duration::MAX);
+  ///   println!("Woken up early.");
+  /// });
+  ///
+  /// let _waker = Task::new().name("waker").create(move |task| {
+  ///   task.delay(Duration::from_millis(10));
+  ///   assert!(sleeper.abort_delay());
+  /// });
+  /// ```
+  #[cfg(freertos_feature = "task_abort_delay")]
+  #[inline]
+  pub fn abort_delay(&self) -> bool {
+    unsafe { crate::shim::xTaskAbortDelay(self.as_ptr()) == pdPASS }
+  }
+
   /// Suspend execution of the task.
   #[inline]
   pub fn suspend(&self) {
@@ -124,29 +290,112 @@ impl TaskHandle {
     }
   }
 
+  /// Send a notification to this task and return its previous notification value.
+  ///
+  /// # Errors
+  ///
+  /// This can only fail when sending [`TaskNotification::SetValue`] and
+  /// the task already has pending notifications.
+  pub fn notify_and_query(&self, notification: TaskNotification) -> Result<u32, FreeRtosError> {
+    let (value, action) = notification.to_freertos();
+
+    let mut previous_value = 0;
+    match unsafe { freertos_rs_task_notify_and_query(self.as_ptr(), value, action, &mut previous_value) } {
+      pdPASS => Ok(previous_value),
+      _ => Err(FreeRtosError::QueueFull),
+    }
+  }
+
+  /// Send a notification to this task from an interrupt and return its previous notification value.
+  ///
+  /// Whether a task with a higher priority than the interrupted task was woken is
+  /// accumulated in `ic`, so a context switch is requested when `ic` is dropped.
+  ///
+  /// # Errors
+  ///
+  /// This can only fail when sending [`TaskNotification::SetValue`] and
+  /// the task already has pending notifications.
+  pub fn notify_and_query_from_isr(
+    &self,
+    notification: TaskNotification,
+    ic: &InterruptContext,
+  ) -> Result<u32, FreeRtosError> {
+    let (value, action) = notification.to_freertos();
+
+    let mut previous_value = 0;
+    let (res, _) = ic.track_woken(|woken| unsafe {
+      freertos_rs_task_notify_and_query_from_isr(
+        self.as_ptr(),
+        value,
+        action,
+        &mut previous_value,
+        woken,
+      )
+    });
+
+    match res {
+      pdPASS => Ok(previous_value),
+      _ => Err(FreeRtosError::QueueFull),
+    }
+  }
+
+  /// Give a notification to this task, incrementing its notification value.
+  ///
+  /// This is a lightweight alternative to a counting semaphore, to be used
+  /// together with [`CurrentTask::take_notification`](crate::task::CurrentTask::take_notification).
+  #[inline]
+  pub fn notify_give(&self) {
+    unsafe { freertos_rs_task_notify_give_indexed(self.as_ptr(), 0) }
+  }
+
+  /// Give a notification with the given index to this task, incrementing its notification value.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `index` is not within \[0, `configTASK_NOTIFICATION_ARRAY_ENTRIES`\).
+  pub fn notify_give_indexed(&self, index: usize) {
+    assert!(index < configTASK_NOTIFICATION_ARRAY_ENTRIES as _);
+
+    unsafe { freertos_rs_task_notify_give_indexed(self.as_ptr(), index as _) }
+  }
+
   /// Notify this task from an interrupt.
+  ///
+  /// Returns whether notifying the task woke a task with a higher priority than the
+  /// interrupted task. This is also accumulated in `ic`, so a context switch is
+  /// requested when `ic` is dropped either way.
+  ///
+  /// # Errors
+  ///
+  /// This can only fail when sending [`TaskNotification::SetValue`] and
+  /// the task already has pending notifications.
   pub fn notify_from_isr(
     &self,
     notification: TaskNotification,
     ic: &InterruptContext,
-  ) -> Result<(), FreeRtosError> {
+  ) -> Result<bool, FreeRtosError> {
     let (value, action) = notification.to_freertos();
 
-    match unsafe {
+    let (res, woken) = ic.track_woken(|woken| unsafe {
       xTaskNotifyFromISR(
         self.as_ptr(),
         value,
         action,
-        ic.as_ptr(),
+        woken,
       )
-    } {
-      pdPASS => Ok(()),
+    });
+
+    match res {
+      pdPASS => Ok(woken),
       _ => Err(FreeRtosError::QueueFull),
     }
   }
 
   /// Notify this task from an interrupt with the given index.
   ///
+  /// Returns whether notifying the task woke a task with a higher priority than the
+  /// interrupted task, see [`notify_from_isr`](TaskHandle::notify_from_isr).
+  ///
   /// # Errors
   ///
   /// This can only fail when sending [`TaskNotification::SetValue`] and
@@ -160,21 +409,23 @@ impl TaskHandle {
     index: usize,
     notification: TaskNotification,
     ic: &InterruptContext,
-  ) -> Result<(), FreeRtosError> {
+  ) -> Result<bool, FreeRtosError> {
     assert!(index < configTASK_NOTIFICATION_ARRAY_ENTRIES as _);
 
     let (value, action) = notification.to_freertos();
 
-    match unsafe {
+    let (res, woken) = ic.track_woken(|woken| unsafe {
       freertos_rs_task_notify_indexed_from_isr(
         self.as_ptr(),
         index as _,
         value,
         action,
-        ic.as_ptr(),
+        woken,
       )
-    } {
-      pdPASS => Ok(()),
+    });
+
+    match res {
+      pdPASS => Ok(woken),
       _ => Err(FreeRtosError::QueueFull),
     }
   }
@@ -192,4 +443,32 @@ impl TaskHandle {
   pub fn clear_notification(&self) -> bool {
     unsafe { xTaskNotifyStateClear(self.as_ptr()) == pdTRUE }
   }
+
+  /// Get the lowest address of this task's stack.
+  #[cfg(freertos_feature = "trace_facility")]
+  fn stack_base(&self) -> *mut StackType_t {
+    // The canary is at the lowest address, so the stack must grow downwards.
+    assert!(portSTACK_GROWTH < 0);
+
+    let mut status = MaybeUninit::<TaskStatus_t>::uninit();
+
+    unsafe {
+      vTaskGetInfo(self.as_ptr(), status.as_mut_ptr(), pdFALSE, eTaskState_eInvalid);
+      status.assume_init().pxStackBase
+    }
+  }
+
+  /// Check whether the stack canary of this task is still intact.
+  ///
+  /// This checks that the last [`STACK_CANARY_LEN`] words of the stack still contain the
+  /// [`STACK_CANARY`] fill written by FreeRTOS when creating the task. Returns `false` if the
+  /// task has used its whole stack, including the canary reserved using
+  /// [`TaskBuilder::stack_canary`](crate::task::TaskBuilder::stack_canary).
+  ///
+  /// This requires `configUSE_TRACE_FACILITY` and is only supported on ports where the stack grows downwards.
+  #[cfg(freertos_feature = "trace_facility")]
+  pub fn check_canary(&self) -> bool {
+    let canary = unsafe { slice::from_raw_parts(self.stack_base(), STACK_CANARY_LEN) };
+    canary.iter().all(|&word| word == STACK_CANARY)
+  }
 }
@@ -18,7 +18,7 @@
 
 use core::{
   ops::Deref,
-  mem::MaybeUninit,
+  mem::{self, MaybeUninit},
 };
 
 #[cfg(freertos_feature = "dynamic_allocation")]
@@ -42,17 +42,52 @@ pub use notification::TaskNotification;
 mod priority;
 pub use priority::TaskPriority;
 mod scheduler;
-pub use scheduler::{SchedulerState, Scheduler};
+pub use scheduler::{SchedulerLock, SchedulerState, Scheduler};
+#[cfg(all(feature = "sync", freertos_feature = "dynamic_allocation"))]
+mod spawn;
+#[cfg(all(feature = "sync", freertos_feature = "dynamic_allocation"))]
+pub use spawn::{spawn, JoinHandle};
 mod stack_overflow_hook;
 pub use stack_overflow_hook::set_stack_overflow_hook;
 mod state;
 pub use state::TaskState;
 mod system_state;
 pub use system_state::{SystemState, TaskStatus};
+#[cfg(all(freertos_feature = "smp", freertos_feature = "run_time_stats"))]
+mod utilization;
+#[cfg(all(freertos_feature = "smp", freertos_feature = "run_time_stats"))]
+pub use utilization::CoreUtilization;
 
 /// Minimal task stack size.
 pub const MINIMAL_STACK_SIZE: usize = configMINIMAL_STACK_SIZE as usize;
 
+/// Value FreeRTOS fills the stack of new tasks with, i.e. `tskSTACK_FILL_BYTE` in every byte.
+///
+/// See [`TaskBuilder::stack_canary`].
+#[cfg(freertos_feature = "trace_facility")]
+pub const STACK_CANARY: StackType_t = StackType_t::MAX / 0xff * 0xa5;
+
+/// Number of [`STACK_CANARY`] words at the end of a task's stack which are checked
+/// by [`TaskHandle::check_canary`].
+#[cfg(freertos_feature = "trace_facility")]
+pub const STACK_CANARY_LEN: usize = 4;
+
+/// Get the index of the core the calling task is running on.
+///
+/// This is always `0` on single-core builds.
+#[inline]
+pub fn current_core() -> usize {
+  #[cfg(freertos_feature = "smp")]
+  {
+    unsafe { crate::shim::freertos_rs_get_core_id() as usize }
+  }
+
+  #[cfg(not(freertos_feature = "smp"))]
+  {
+    0
+  }
+}
+
 /// A task.
 #[must_use = "task will be deleted immediately if unused"]
 pub struct Task {
@@ -75,6 +110,40 @@ impl Task {
   pub fn idle_task() -> &'static TaskHandle {
     unsafe { TaskHandle::from_ptr(xTaskGetIdleTaskHandle()) }
   }
+
+  /// Get the handle for the idle task of the given `core`.
+  ///
+  /// # Panics
+  ///
+  /// This panics if `core` is not less than [`Scheduler::num_cores`].
+  #[cfg(freertos_feature = "smp")]
+  pub fn idle_task_for_core(core: usize) -> &'static TaskHandle {
+    assert!(core < Scheduler::num_cores());
+    unsafe { TaskHandle::from_ptr(crate::shim::xTaskGetIdleTaskHandleForCore(core as _)) }
+  }
+
+  /// Detach the task so it keeps running after the `Task` goes out of scope.
+  ///
+  /// If the task function returns, the task deletes itself and its stack is freed.
+  /// Otherwise, the task stays allocated for the lifetime of the program. For dynamically
+  /// created tasks, the small allocation holding the task function is always leaked.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::task::Task;
+  ///
+  /// Task::new().name("blink").create(|task| loop {
+  ///   // ...
+  ///   task.delay(Duration::from_millis(500));
+  /// }).detach();
+  /// ```
+  #[inline]
+  pub fn detach(self) {
+    mem::forget(self);
+  }
 }
 
 impl Deref for Task {
@@ -93,7 +162,11 @@ impl Drop for Task {
 }
 
 /// A statically allocated task.
-pub struct StaticTask<const STACK_SIZE: usize = MINIMAL_STACK_SIZE> {
+///
+/// `F` is the type of the task function for tasks created using
+/// [`TaskBuilder::create_static_with`], which stores it alongside the stack.
+pub struct StaticTask<const STACK_SIZE: usize = MINIMAL_STACK_SIZE, F = ()> {
   data: StaticTask_t,
   stack: [MaybeUninit<StackType_t>; STACK_SIZE],
+  function: MaybeUninit<F>,
 }
@@ -1,4 +1,8 @@
-use core::mem::MaybeUninit;
+use core::{marker::PhantomData, mem::{self, MaybeUninit}};
+#[cfg(freertos_feature = "trace_facility")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "time")]
+use core::time::Duration;
 
 use alloc2::vec::Vec;
 
@@ -13,6 +17,7 @@ use crate::shim::{
   vTaskSuspendAll,
   xTaskResumeAll,
   xTaskGetTickCount,
+  xTaskGetTickCountFromISR,
   uxTaskGetSystemState,
   uxTaskGetNumberOfTasks,
   pdTRUE,
@@ -20,6 +25,9 @@ use crate::shim::{
 };
 
 use crate::ticks::Ticks;
+use crate::InterruptContext;
+#[cfg(feature = "time")]
+use crate::time::Instant;
 
 use super::{
   TaskName,
@@ -51,14 +59,59 @@ impl SchedulerState {
   }
 }
 
+#[cfg(freertos_feature = "trace_facility")]
+static CONTEXT_SWITCHES: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(freertos_feature = "trace_facility")]
+#[export_name = "freertos_rs_task_switched_in"]
+extern "C" fn task_switched_in() {
+  CONTEXT_SWITCHES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// An RAII guard which keeps the scheduler suspended, returned by [`Scheduler::lock`].
+///
+/// The scheduler is resumed when the guard is dropped.
+#[must_use = "scheduler is resumed immediately if unused"]
+pub struct SchedulerLock {
+  // Suspending the scheduler is tied to the current task.
+  _not_send: PhantomData<*const ()>,
+}
+
+impl SchedulerLock {
+  /// Resume the scheduler.
+  ///
+  /// Returns `true` if resuming the scheduler caused a context switch.
+  #[inline]
+  pub fn unlock(self) -> bool {
+    mem::forget(self);
+    Scheduler::resume()
+  }
+}
+
+impl Drop for SchedulerLock {
+  #[inline]
+  fn drop(&mut self) {
+    Scheduler::resume();
+  }
+}
+
 /// The FreeRTOS task scheduler.
 #[non_exhaustive]
 pub struct Scheduler;
 
 impl Scheduler {
   /// Start scheduling tasks.
+  ///
+  /// In debug builds, the hook set using [`set_no_tasks_hook`](crate::set_no_tasks_hook)
+  /// is called if no tasks were created before starting the scheduler.
   #[inline(always)]
   pub fn start() -> ! {
+    // The idle and timer tasks are only created by `vTaskStartScheduler`,
+    // so all tasks existing at this point are user tasks.
+    if cfg!(debug_assertions) && Self::task_count() == 0 {
+      crate::hooks::no_tasks_hook();
+    }
+
     unsafe { vTaskStartScheduler() };
     unreachable!()
   }
@@ -85,17 +138,112 @@ impl Scheduler {
     unsafe { xTaskResumeAll() == pdTRUE }
   }
 
+  /// Suspend the scheduler until the returned guard is dropped.
+  ///
+  /// This is a safer alternative to pairing [`suspend`](Scheduler::suspend) and
+  /// [`resume`](Scheduler::resume) manually.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use freertos_rust::task::Scheduler;
+  ///
+  /// let lock = Scheduler::lock();
+  /// // No other task can run here.
+  /// let switched = lock.unlock();
+  /// ```
+  #[inline]
+  pub fn lock() -> SchedulerLock {
+    Self::suspend();
+    SchedulerLock { _not_send: PhantomData }
+  }
+
+  /// Get the number of cores the scheduler runs tasks on.
+  ///
+  /// This is `configNUMBER_OF_CORES` on SMP builds and `1` otherwise.
+  #[inline(always)]
+  pub const fn num_cores() -> usize {
+    #[cfg(freertos_feature = "smp")]
+    {
+      crate::shim::configNUMBER_OF_CORES as usize
+    }
+
+    #[cfg(not(freertos_feature = "smp"))]
+    {
+      1
+    }
+  }
+
   /// Number of ticks since the scheduler was started.
   #[inline(always)]
   pub fn tick_count() -> Ticks {
     unsafe { xTaskGetTickCount().into() }
   }
 
+  /// Number of ticks since the scheduler was started, from an interrupt service routine.
+  #[inline(always)]
+  pub fn tick_count_from_isr(_ic: &InterruptContext) -> Ticks {
+    unsafe { xTaskGetTickCountFromISR().into() }
+  }
+
+  /// Time elapsed since the scheduler was started.
+  ///
+  /// Unlike [`tick_count`](Scheduler::tick_count), this does not wrap around.
+  #[cfg(feature = "time")]
+  #[inline]
+  pub fn uptime() -> Duration {
+    Instant::now().since_start()
+  }
+
+  /// Number of context switches since the scheduler was started.
+  ///
+  /// Context switches are counted using the `traceTASK_SWITCHED_IN` trace macro,
+  /// which can only be defined in C. Add the following to `FreeRTOSConfig.h`,
+  /// otherwise this always returns `0`:
+  ///
+  /// ```c
+  /// extern void freertos_rs_task_switched_in(void);
+  /// #define traceTASK_SWITCHED_IN() freertos_rs_task_switched_in()
+  /// ```
+  #[cfg(freertos_feature = "trace_facility")]
+  #[inline]
+  pub fn context_switches() -> usize {
+    CONTEXT_SWITCHES.load(Ordering::Relaxed)
+  }
+
   /// Get the number of existing tasks.
   pub fn task_count() -> usize {
     unsafe { uxTaskGetNumberOfTasks() as usize }
   }
 
+  /// Write the run time statistics table generated by FreeRTOS into `buf`.
+  ///
+  /// Returns the written part of `buf`. The output is truncated if `buf` is too small;
+  /// FreeRTOS recommends about 40 bytes per task.
+  ///
+  /// This requires `configGENERATE_RUN_TIME_STATS`, `configUSE_STATS_FORMATTING_FUNCTIONS`
+  /// and `configUSE_TRACE_FACILITY`.
+  #[cfg(all(
+    freertos_feature = "run_time_stats",
+    freertos_feature = "stats_formatting",
+    freertos_feature = "run_time_statistics",
+    freertos_feature = "trace_facility",
+  ))]
+  pub fn run_time_stats(buf: &mut [u8]) -> &str {
+    if buf.is_empty() {
+      return ""
+    }
+
+    buf[0] = 0;
+
+    let len = unsafe {
+      crate::shim::vTaskGetRunTimeStatistics(buf.as_mut_ptr().cast(), buf.len() as _);
+      core::ffi::CStr::from_ptr(buf.as_ptr().cast()).to_bytes().len()
+    };
+
+    core::str::from_utf8(&buf[..len]).unwrap_or_default()
+  }
+
   /// Get the complete system state.
   pub fn system_state() -> SystemState {
     let tasks_len = Self::task_count();
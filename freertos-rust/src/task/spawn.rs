@@ -0,0 +1,79 @@
+use core::mem::ManuallyDrop;
+
+use alloc2::sync::Arc;
+
+use crate::{
+  shim::portMAX_DELAY,
+  sync::Queue,
+  Ticks,
+};
+
+use super::{Task, TaskHandle};
+
+/// Spawn a new task running `f`, returning a [`JoinHandle`] for it.
+///
+/// This provides a `std::thread::spawn`-like interface on top of [`TaskBuilder`](crate::task::TaskBuilder),
+/// so the same code can run on hosted ports for testing as well as on embedded targets.
+/// The task is created with the default stack size and priority.
+///
+/// # Examples
+///
+/// ```no_run
+/// use freertos_rust::task;
+///
+/// let handle = task::spawn("worker", || {
+///   // ...
+///   42
+/// });
+///
+/// assert_eq!(handle.join(), 42);
+/// ```
+pub fn spawn<F, T>(name: &str, f: F) -> JoinHandle<T>
+where
+  F: FnOnce() -> T + Send + 'static,
+  T: Send + 'static,
+{
+  let result: Arc<Queue<T, 1>> = Arc::new(Queue::new());
+
+  let task = {
+    let result = Arc::clone(&result);
+
+    Task::new().name(name).create(move |_| {
+      let _ = result.send(f(), Ticks::new(portMAX_DELAY));
+    })
+  };
+
+  JoinHandle { task: ManuallyDrop::new(task), result }
+}
+
+/// An owned permission to join on a task spawned using [`spawn`].
+///
+/// Dropping a `JoinHandle` detaches the task. The task deletes itself once it returns.
+#[must_use = "dropping a `JoinHandle` detaches the task"]
+pub struct JoinHandle<T> {
+  task: ManuallyDrop<Task>,
+  result: Arc<Queue<T, 1>>,
+}
+
+impl<T: Send> JoinHandle<T> {
+  /// Get the handle of the spawned task.
+  #[inline]
+  pub fn task(&self) -> &TaskHandle {
+    &self.task
+  }
+
+  /// Wait for the task to return and get its result.
+  pub fn join(mut self) -> T {
+    let value = loop {
+      if let Ok(value) = self.result.receive(Ticks::new(portMAX_DELAY)) {
+        break value
+      }
+    };
+
+    // The task function has returned, so the task no longer accesses its boxed
+    // function. The task deletes itself, so only the function is dropped here.
+    drop(self.task.function.take());
+
+    value
+  }
+}
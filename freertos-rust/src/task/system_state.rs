@@ -1,9 +1,6 @@
 use core::{fmt, ffi::{c_ulong, c_ushort}};
 
-use alloc2::{
-  string::ToString,
-  vec::Vec,
-};
+use alloc2::vec::Vec;
 
 use crate::{
   shim::UBaseType_t,
@@ -68,8 +65,47 @@ pub struct SystemState {
   pub(crate) total_run_time: c_ulong,
 }
 
-impl fmt::Display for SystemState {
-  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+impl SystemState {
+  /// Get the task closest to exhausting its stack together with its
+  /// stack high water mark in words.
+  ///
+  /// Returns `None` if there are no tasks.
+  ///
+  /// This is useful for a periodic health check, e.g.
+  ///
+  /// ```no_run
+  /// use freertos_rust::task::Scheduler;
+  ///
+  /// let state = Scheduler::system_state();
+  ///
+  /// if let Some((task, words_left)) = state.min_stack_high_water() {
+  ///   if words_left < 16 {
+  ///     panic!("task {} is about to overflow its stack", task.name());
+  ///   }
+  /// }
+  /// ```
+  pub fn min_stack_high_water(&self) -> Option<(&TaskHandle, usize)> {
+    self.tasks.iter()
+      .min_by_key(|task| task.stack_high_water_mark)
+      .map(|task| (task.task(), task.stack_high_water_mark as usize))
+  }
+
+  /// Get the run time of `task` in percent of the total run time.
+  ///
+  /// Returns `None` if no run time statistics are available.
+  fn run_time_percentage(&self, task: &TaskStatus) -> Option<u32> {
+    if self.total_run_time > 0 && task.run_time_counter <= self.total_run_time {
+      Some((((task.run_time_counter as u64) * 100) / self.total_run_time as u64) as u32)
+    } else {
+      None
+    }
+  }
+
+  /// Write the run time statistics table into `f`.
+  ///
+  /// This produces the same output as the [`Display`](fmt::Display) implementation
+  /// but does not allocate.
+  pub fn write_runtime_stats<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
     f.write_str("FreeRTOS tasks\r\n")?;
 
     writeln!(
@@ -85,27 +121,22 @@ impl fmt::Display for SystemState {
     )?;
 
     for task in &self.tasks {
-      writeln!(
+      write!(
         f,
-        "{id: <6} | {name: <16} | {state: <9} | {priority: <8} | {stack: >10} | {cpu_abs: >10} | {cpu_rel: >4}",
+        "{id: <6} | {name: <16} | {state: <9} | {priority: <8} | {stack: >10} | {cpu_abs: >10} | ",
         id = task.number(),
         name = task.name(),
-        state = format!("{:?}", task.state()),
+        state = task_state_name(task.state()),
         priority = task.current_priority,
         stack = task.stack_high_water_mark,
         cpu_abs = task.run_time_counter,
-        cpu_rel = if self.total_run_time > 0 && task.run_time_counter <= self.total_run_time {
-          let p = (((task.run_time_counter as u64) * 100) / self.total_run_time as u64) as u32;
-          let ps = if p == 0 && task.run_time_counter > 0 {
-            "<1".to_string()
-          } else {
-            p.to_string()
-          };
-          format!("{: >3}%", ps)
-        } else {
-          "-".to_string()
-        }
       )?;
+
+      match self.run_time_percentage(task) {
+        Some(0) if task.run_time_counter > 0 => writeln!(f, "{: >3}%", "<1")?,
+        Some(p) => writeln!(f, "{: >3}%", p)?,
+        None => writeln!(f, "{: >4}", "-")?,
+      }
     }
 
     if self.total_run_time > 0 {
@@ -115,3 +146,20 @@ impl fmt::Display for SystemState {
     Ok(())
   }
 }
+
+const fn task_state_name(state: TaskState) -> &'static str {
+  match state {
+    TaskState::Running => "Running",
+    TaskState::Ready => "Ready",
+    TaskState::Blocked => "Blocked",
+    TaskState::Suspended => "Suspended",
+    TaskState::Deleted => "Deleted",
+    TaskState::Invalid => "Invalid",
+  }
+}
+
+impl fmt::Display for SystemState {
+  fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    self.write_runtime_stats(f)
+  }
+}
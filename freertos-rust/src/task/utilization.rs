@@ -0,0 +1,111 @@
+use crate::shim::{freertos_rs_get_run_time_counter, freertos_rs_run_time_counter_t, ulTaskGetRunTimeCounter};
+
+use super::{Scheduler, Task};
+
+const NUM_CORES: usize = Scheduler::num_cores();
+
+/// Per-core CPU utilization, derived from the run time of the idle task of each core.
+///
+/// Every call to [`sample`](CoreUtilization::sample) measures the utilization since the
+/// previous sample, so it should be called periodically, e.g. from a monitoring task.
+/// Since FreeRTOS only updates the run time of a task when it is switched out, the
+/// sampling period should be long compared to the time slice.
+///
+/// This requires `configNUMBER_OF_CORES > 1`, `configGENERATE_RUN_TIME_STATS`
+/// and `INCLUDE_xTaskGetIdleTaskHandle`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use core::time::Duration;
+///
+/// use freertos_rust::task::{CoreUtilization, Task};
+///
+/// let _monitor = Task::new().name("monitor").create(|task| {
+///   let mut utilization = CoreUtilization::new();
+///
+///   loop {
+///     task.delay(Duration::from_secs(1));
+///
+///     for (core, load) in utilization.sample().iter().enumerate() {
+///       println!("core {}: {:.1}%", core, load * 100.0);
+///     }
+///   }
+/// });
+/// ```
+#[derive(Debug)]
+pub struct CoreUtilization {
+  idle: [freertos_rs_run_time_counter_t; NUM_CORES],
+  total: freertos_rs_run_time_counter_t,
+}
+
+impl CoreUtilization {
+  /// Start measuring the utilization of all cores.
+  #[allow(clippy::new_without_default)]
+  pub fn new() -> Self {
+    let (idle, total) = Self::counters();
+    Self { idle, total }
+  }
+
+  fn counters() -> ([freertos_rs_run_time_counter_t; NUM_CORES], freertos_rs_run_time_counter_t) {
+    let mut idle = [0; NUM_CORES];
+
+    for (core, idle) in idle.iter_mut().enumerate() {
+      *idle = unsafe { ulTaskGetRunTimeCounter(Task::idle_task_for_core(core).as_ptr()) };
+    }
+
+    (idle, unsafe { freertos_rs_get_run_time_counter() })
+  }
+
+  /// Get the utilization of each core since the previous sample.
+  ///
+  /// The utilization is the fraction of time a core did not run its idle task, within \[0, 1\].
+  pub fn sample(&mut self) -> [f32; NUM_CORES] {
+    let (idle, total) = Self::counters();
+    let elapsed = total.wrapping_sub(self.total);
+
+    let mut utilization = [0.0; NUM_CORES];
+
+    if elapsed > 0 {
+      for (core, utilization) in utilization.iter_mut().enumerate() {
+        let idle_time = idle[core].wrapping_sub(self.idle[core]);
+        *utilization = 1.0 - (idle_time as f32 / elapsed as f32).min(1.0);
+      }
+    }
+
+    self.idle = idle;
+    self.total = total;
+
+    utilization
+  }
+
+  /// Sample the utilization of each core and pass it to `rebalance`.
+  ///
+  /// This is a hook for adjusting task affinities based on load, e.g. using
+  /// [`TaskHandle::set_core_affinity`](crate::task::TaskHandle::set_core_affinity).
+  ///
+  /// This additionally requires `configUSE_CORE_AFFINITY`.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::task::{CoreUtilization, Task, TaskHandle};
+  ///
+  /// fn balance(utilization: &mut CoreUtilization, worker: &TaskHandle) {
+  ///   utilization.rebalance(|load| {
+  ///     let least_loaded = (0..load.len())
+  ///       .min_by(|&a, &b| load[a].total_cmp(&load[b]))
+  ///       .unwrap();
+  ///
+  ///     worker.set_core_affinity(1 << least_loaded);
+  ///   });
+  /// }
+  /// ```
+  #[cfg(freertos_feature = "core_affinity")]
+  pub fn rebalance<R>(&mut self, rebalance: impl FnOnce(&[f32; NUM_CORES]) -> R) -> R {
+    let utilization = self.sample();
+    rebalance(&utilization)
+  }
+}
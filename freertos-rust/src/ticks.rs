@@ -3,6 +3,8 @@ use core::time::Duration;
 use crate::shim::{portMAX_DELAY, portTICK_PERIOD_MS};
 use crate::ffi::TickType_t;
 
+const TICK_PERIOD_NS: u128 = portTICK_PERIOD_MS as u128 * 1_000_000;
+
 /// Duration in FreeRTOS ticks.
 ///
 /// This type represents a duration in ticks. The duration of a single tick
@@ -15,6 +17,16 @@ use crate::ffi::TickType_t;
 /// - `Duration::ZERO` makes an API call non-blocking and it will return immediately.
 /// - `Duration::MAX` blocks an API call until it completes. This is true for any
 ///   `Duration` which exceeds `portMAX_DELAY` ticks.
+///
+/// Converting a [`Duration`] using [`From`] rounds up to whole ticks, so a timeout
+/// is never shorter than requested. In particular, a non-zero duration shorter than
+/// a single tick is converted to one tick instead of making the call non-blocking.
+/// Use [`from_duration_floor`](Ticks::from_duration_floor) or
+/// [`from_duration_round`](Ticks::from_duration_round) for other rounding modes.
+///
+/// Conversions from milliseconds or microseconds round down to whole ticks.
+/// Conversely, `Ticks` are converted to a [`Duration`] exactly, with `portMAX_DELAY`
+/// being converted to `Duration::MAX`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(transparent)]
 pub struct Ticks {
@@ -37,6 +49,53 @@ impl Ticks {
   pub const fn from_secs(s: u32) -> Self {
     Self::from_millis(s * 1000)
   }
+
+  /// Create `Ticks` from microseconds.
+  pub const fn from_micros(us: u32) -> Self {
+    let ticks = us as u64 / (portTICK_PERIOD_MS as u64 * 1000);
+    Self { ticks: ticks as TickType_t }
+  }
+
+  /// Create `Ticks` from a [`Duration`], rounding down to whole ticks.
+  ///
+  /// Durations exceeding `portMAX_DELAY` ticks saturate at `portMAX_DELAY`.
+  pub const fn from_duration_floor(duration: Duration) -> Self {
+    Self::from_nanos(duration.as_nanos())
+  }
+
+  /// Create `Ticks` from a [`Duration`], rounding up to whole ticks.
+  ///
+  /// Durations exceeding `portMAX_DELAY` ticks saturate at `portMAX_DELAY`.
+  pub const fn from_duration_ceil(duration: Duration) -> Self {
+    Self::from_nanos(duration.as_nanos() + (TICK_PERIOD_NS - 1))
+  }
+
+  /// Create `Ticks` from a [`Duration`], rounding to the nearest whole tick.
+  ///
+  /// Durations exceeding `portMAX_DELAY` ticks saturate at `portMAX_DELAY`.
+  pub const fn from_duration_round(duration: Duration) -> Self {
+    Self::from_nanos(duration.as_nanos() + TICK_PERIOD_NS / 2)
+  }
+
+  const fn from_nanos(ns: u128) -> Self {
+    let ticks = ns / TICK_PERIOD_NS;
+    Self::new(if ticks > portMAX_DELAY as u128 { portMAX_DELAY } else { ticks as TickType_t })
+  }
+
+  /// Get the number of whole milliseconds, saturating at `u32::MAX`.
+  pub const fn as_millis(&self) -> u32 {
+    let ms = self.ticks as u64 * portTICK_PERIOD_MS as u64;
+    if ms > u32::MAX as u64 { u32::MAX } else { ms as u32 }
+  }
+
+  /// Subtract `other` from `self`, wrapping around at the boundary of [`TickType_t`].
+  ///
+  /// This computes the correct number of ticks between two tick counts, even if
+  /// the tick count overflowed in between, as long as less than [`TickType_t::MAX`]
+  /// ticks have elapsed.
+  pub const fn wrapping_sub(self, other: Self) -> Self {
+    Self::new(self.ticks.wrapping_sub(other.ticks))
+  }
 }
 
 impl From<Ticks> for TickType_t {
@@ -51,11 +110,25 @@ impl From<TickType_t> for Ticks {
   }
 }
 
+impl From<Ticks> for Duration {
+  /// Convert `Ticks` to a `Duration`.
+  ///
+  /// `portMAX_DELAY` is converted to `Duration::MAX`.
+  fn from(ticks: Ticks) -> Self {
+    if ticks.ticks == portMAX_DELAY {
+      return Duration::MAX
+    }
+
+    Duration::from_millis(ticks.ticks as u64 * portTICK_PERIOD_MS as u64)
+  }
+}
+
 impl From<Duration> for Ticks {
-  /// Convert a `Duration` to `Ticks`.
+  /// Convert a `Duration` to `Ticks`, rounding up.
+  ///
+  /// See [`Ticks::from_duration_ceil`].
   fn from(duration: Duration) -> Self {
-    let ticks = duration.as_millis() / portTICK_PERIOD_MS as u128;
-    Self::new(ticks.try_into().unwrap_or(portMAX_DELAY))
+    Self::from_duration_ceil(duration)
   }
 }
 
@@ -67,4 +140,28 @@ mod tests {
   fn duration_max_gte_port_max_delay() {
     assert_eq!(Ticks::from(Duration::MAX), Ticks::new(portMAX_DELAY));
   }
+
+  #[test]
+  fn duration_rounding() {
+    let period = Duration::from_millis(portTICK_PERIOD_MS as u64);
+    let half = period / 2;
+    let nanosecond = Duration::from_nanos(1);
+
+    assert_eq!(Ticks::from(Duration::ZERO), Ticks::new(0));
+    assert_eq!(Ticks::from(nanosecond), Ticks::new(1));
+    assert_eq!(Ticks::from(period), Ticks::new(1));
+    assert_eq!(Ticks::from(period + nanosecond), Ticks::new(2));
+
+    assert_eq!(Ticks::from_duration_floor(period - nanosecond), Ticks::new(0));
+    assert_eq!(Ticks::from_duration_floor(period), Ticks::new(1));
+
+    assert_eq!(Ticks::from_duration_round(half - nanosecond), Ticks::new(0));
+    assert_eq!(Ticks::from_duration_round(half), Ticks::new(1));
+    assert_eq!(Ticks::from_duration_round(period + half - nanosecond), Ticks::new(1));
+  }
+
+  #[test]
+  fn port_max_delay_to_duration_max() {
+    assert_eq!(Duration::from(Ticks::new(portMAX_DELAY)), Duration::MAX);
+  }
 }
@@ -0,0 +1,184 @@
+//! Temporal quantification.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use freertos_rust::time::Instant;
+//!
+//! let start = Instant::now();
+//! // ...
+//! println!("Took {:?}.", start.elapsed());
+//! ```
+
+use core::{
+  mem::MaybeUninit,
+  ops::{Add, AddAssign, Sub, SubAssign},
+  time::Duration,
+};
+
+use crate::{
+  ffi::TickType_t,
+  shim::{portTICK_PERIOD_MS, vTaskSetTimeOutState, TimeOut_t},
+  task::Scheduler,
+  Ticks,
+};
+
+/// A measurement of a monotonically nondecreasing clock.
+///
+/// An `Instant` is a 64-bit tick count which combines the FreeRTOS tick count
+/// with the number of times the tick count has overflowed, so it does not wrap
+/// around like [`Scheduler::tick_count`](crate::task::Scheduler::tick_count).
+///
+/// The resolution of an `Instant` is a single tick, i.e. `portTICK_PERIOD_MS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant {
+  ticks: u64,
+}
+
+const fn ticks_to_duration(ticks: u64) -> Duration {
+  Duration::from_millis(ticks * portTICK_PERIOD_MS as u64)
+}
+
+fn duration_to_ticks(duration: Duration) -> Option<u64> {
+  (duration.as_millis() / portTICK_PERIOD_MS as u128).try_into().ok()
+}
+
+impl Instant {
+  /// Returns an instant corresponding to “now”.
+  ///
+  /// This must not be called from an interrupt service routine.
+  pub fn now() -> Self {
+    let mut time_out = MaybeUninit::<TimeOut_t>::uninit();
+
+    let time_out = unsafe {
+      vTaskSetTimeOutState(time_out.as_mut_ptr());
+      time_out.assume_init()
+    };
+
+    let overflows = (time_out.xOverflowCount as u64).checked_shl(TickType_t::BITS).unwrap_or(0);
+    Self { ticks: overflows | time_out.xTimeOnEntering as u64 }
+  }
+
+  /// Returns the amount of time elapsed from another instant to this one,
+  /// or zero duration if that instant is later than this one.
+  pub fn duration_since(&self, earlier: Instant) -> Duration {
+    self.checked_duration_since(earlier).unwrap_or_default()
+  }
+
+  /// Returns the amount of time elapsed from another instant to this one,
+  /// or `None` if that instant is later than this one.
+  pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+    self.ticks.checked_sub(earlier.ticks).map(ticks_to_duration)
+  }
+
+  /// Returns the amount of time elapsed since this instant.
+  pub fn elapsed(&self) -> Duration {
+    Instant::now().duration_since(*self)
+  }
+
+  /// Returns the amount of time elapsed since the scheduler was started.
+  pub(crate) const fn since_start(&self) -> Duration {
+    ticks_to_duration(self.ticks)
+  }
+
+  /// Returns `Some(t)` where `t` is the time `self + duration` if `t` can be represented.
+  pub fn checked_add(&self, duration: Duration) -> Option<Instant> {
+    self.ticks.checked_add(duration_to_ticks(duration)?).map(|ticks| Instant { ticks })
+  }
+
+  /// Returns `Some(t)` where `t` is the time `self - duration` if `t` can be represented.
+  pub fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+    self.ticks.checked_sub(duration_to_ticks(duration)?).map(|ticks| Instant { ticks })
+  }
+}
+
+impl Add<Duration> for Instant {
+  type Output = Instant;
+
+  /// # Panics
+  ///
+  /// This function may panic if the resulting point in time cannot be represented.
+  fn add(self, other: Duration) -> Instant {
+    self.checked_add(other).expect("overflow when adding duration to instant")
+  }
+}
+
+impl AddAssign<Duration> for Instant {
+  fn add_assign(&mut self, other: Duration) {
+    *self = *self + other;
+  }
+}
+
+impl Sub<Duration> for Instant {
+  type Output = Instant;
+
+  fn sub(self, other: Duration) -> Instant {
+    self.checked_sub(other).expect("overflow when subtracting duration from instant")
+  }
+}
+
+impl SubAssign<Duration> for Instant {
+  fn sub_assign(&mut self, other: Duration) {
+    *self = *self - other;
+  }
+}
+
+impl Sub<Instant> for Instant {
+  type Output = Duration;
+
+  /// Returns the amount of time elapsed from another instant to this one,
+  /// or zero duration if that instant is later than this one.
+  fn sub(self, other: Instant) -> Duration {
+    self.duration_since(other)
+  }
+}
+
+/// A lightweight stopwatch for measuring short durations.
+///
+/// A `Stopwatch` only captures the current tick count, so elapsed time is
+/// computed using wrapping subtraction. This is correct as long as less than
+/// [`TickType_t::MAX`] ticks have elapsed since it was started. For longer
+/// durations, use [`Instant`] instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use freertos_rust::time::Stopwatch;
+///
+/// let stopwatch = Stopwatch::start();
+/// // ...
+/// println!("Took {:?}.", stopwatch.elapsed());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stopwatch {
+  start: Ticks,
+}
+
+impl Stopwatch {
+  /// Start a new stopwatch at the current tick count.
+  #[inline]
+  pub fn start() -> Self {
+    Self { start: Scheduler::tick_count() }
+  }
+
+  /// Restart the stopwatch, returning the time elapsed until now.
+  #[inline]
+  pub fn restart(&mut self) -> Duration {
+    let now = Scheduler::tick_count();
+    let elapsed = now.wrapping_sub(self.start);
+    self.start = now;
+    ticks_to_duration(elapsed.ticks as u64)
+  }
+
+  /// Returns the number of ticks elapsed since the stopwatch was started.
+  #[inline]
+  pub fn elapsed_ticks(&self) -> Ticks {
+    Scheduler::tick_count().wrapping_sub(self.start)
+  }
+
+  /// Returns the amount of time elapsed since the stopwatch was started.
+  #[inline]
+  pub fn elapsed(&self) -> Duration {
+    ticks_to_duration(self.elapsed_ticks().ticks as u64)
+  }
+}
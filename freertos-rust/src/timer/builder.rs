@@ -1,4 +1,4 @@
-use core::{ptr, ffi::CStr};
+use core::{ptr, ffi::CStr, time::Duration};
 #[cfg(freertos_feature = "static_allocation")]
 use core::mem::{self, MaybeUninit};
 
@@ -8,6 +8,7 @@ use alloc2::boxed::Box;
 use crate::{
   ffi::TimerHandle_t,
   shim::{pdFALSE, pdTRUE, pvTimerGetTimerID},
+  FreeRtosError,
   Ticks,
 };
 #[cfg(freertos_feature = "dynamic_allocation")]
@@ -17,12 +18,12 @@ use crate::shim::xTimerCreateStatic;
 
 use super::{Timer, StaticTimer, TimerHandle};
 #[cfg(freertos_feature = "dynamic_allocation")]
-use super::BoxTimerFn;
+use super::TimerData;
 
 /// Helper struct for creating a new timer returned by [`Timer::new`].
 pub struct TimerBuilder<'n> {
   pub(super) name: Option<&'n CStr>,
-  pub(super) period: Ticks,
+  pub(super) period: Duration,
   pub(super) auto_reload: bool,
 }
 
@@ -37,7 +38,10 @@ impl<'n> TimerBuilder<'n> {
   }
 
   /// Set the period of the timer.
-  pub fn period<T: Into<Ticks>>(mut self, period: T) -> Self {
+  ///
+  /// The period is rounded down to whole ticks and must be at least one tick,
+  /// otherwise creating the timer fails.
+  pub fn period<T: Into<Duration>>(mut self, period: T) -> Self {
     self.period = period.into();
     self
   }
@@ -48,21 +52,121 @@ impl<'n> TimerBuilder<'n> {
     self
   }
 
+  /// Get the period in whole ticks, ensuring it is not shorter than a single tick.
+  fn period_ticks(&self) -> Result<Ticks, FreeRtosError> {
+    let period = Ticks::from_duration_floor(self.period);
+
+    if period.ticks == 0 {
+      return Err(FreeRtosError::InvalidParameter)
+    }
+
+    Ok(period)
+  }
+
   /// Create the dynamic [`Timer`].
   ///
   /// Note that the newly created timer must be started.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if the period is shorter than a
+  /// single tick and [`FreeRtosError::OutOfMemory`] if the timer could not be allocated.
   #[cfg(freertos_feature = "dynamic_allocation")]
-  pub fn create<F>(self, callback: F) -> Timer<'n>
+  pub fn create<F>(self, callback: F) -> Result<Timer<'n>, FreeRtosError>
   where
     F: Fn(&TimerHandle) + Send + 'static,
   {
-    extern "C" fn timer_callback(ptr: TimerHandle_t) {
+    self.create_mut(callback)
+  }
+
+  /// Create the dynamic [`Timer`] with a callback which can mutate its captured state.
+  ///
+  /// Since timer callbacks are only ever called from the timer daemon task, the
+  /// callback is never called concurrently, so no interior mutability is needed.
+  ///
+  /// Note that the newly created timer must be started.
+  ///
+  /// # Errors
+  ///
+  /// See [`create`](TimerBuilder::create).
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::Timer;
+  ///
+  /// let mut led_on = false;
+  ///
+  /// let timer = Timer::new()
+  ///   .period(Duration::from_millis(500))
+  ///   .create_mut(move |_timer| {
+  ///     led_on = !led_on;
+  ///   })
+  ///   .unwrap();
+  ///
+  /// timer.start(Duration::MAX).unwrap();
+  /// ```
+  #[cfg(freertos_feature = "dynamic_allocation")]
+  pub fn create_mut<F>(self, mut callback: F) -> Result<Timer<'n>, FreeRtosError>
+  where
+    F: FnMut(&TimerHandle) + Send + 'static,
+  {
+    self.create_with_id((), move |timer, _| callback(timer))
+  }
+
+  /// Create the dynamic [`Timer`] with a typed ID which is passed to every invocation of `callback`.
+  ///
+  /// The ID is stored together with the callback and can also be accessed
+  /// through [`TimerHandle::id`], which allows a single callback function to
+  /// behave differently for every timer instance.
+  ///
+  /// Note that the newly created timer must be started.
+  ///
+  /// # Errors
+  ///
+  /// See [`create`](TimerBuilder::create).
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::{Timer, TimerHandle};
+  ///
+  /// fn blink(_timer: &TimerHandle, led: &u8) {
+  ///   // Toggle `led`.
+  /// }
+  ///
+  /// let red = Timer::new().period(Duration::from_millis(500)).create_with_id(0u8, blink).unwrap();
+  /// let green = Timer::new().period(Duration::from_millis(300)).create_with_id(1u8, blink).unwrap();
+  ///
+  /// assert_eq!(unsafe { *green.id::<u8>() }, 1);
+  ///
+  /// red.start(Duration::MAX).unwrap();
+  /// green.start(Duration::MAX).unwrap();
+  /// ```
+  #[cfg(freertos_feature = "dynamic_allocation")]
+  pub fn create_with_id<D, F>(self, id: D, callback: F) -> Result<Timer<'n>, FreeRtosError>
+  where
+    D: Send + Sync + 'static,
+    F: FnMut(&TimerHandle, &D) + Send + 'static,
+  {
+    let period = self.period_ticks()?;
+
+    // NOTE: Timer callbacks are only ever called from the timer daemon task,
+    //       so `FnMut` callbacks are never called concurrently.
+    extern "C" fn timer_callback<D, F>(ptr: TimerHandle_t)
+    where
+      F: FnMut(&TimerHandle, &D),
+    {
       unsafe {
         let handle = TimerHandle::from_ptr(ptr);
 
-        let callback_ptr = pvTimerGetTimerID(ptr);
-        let callback: &mut BoxTimerFn = &mut *callback_ptr.cast();
-        callback(handle);
+        let data: *mut TimerData<D, F> = pvTimerGetTimerID(ptr).cast();
+        let callback = &mut *ptr::addr_of_mut!((*data).callback);
+        callback(handle, &*ptr::addr_of!((*data).id));
       }
     }
 
@@ -72,27 +176,75 @@ impl<'n> TimerBuilder<'n> {
       ptr::null()
     };
 
-    let callback = Box::new(callback);
-    let callback_ptr: *mut BoxTimerFn = Box::into_raw(Box::new(callback));
+    let data_ptr = Box::into_raw(Box::new(TimerData { id, callback }));
 
     unsafe {
       let ptr = xTimerCreate(
         name,
-        self.period.ticks,
+        period.ticks,
         if self.auto_reload { pdTRUE } else { pdFALSE } as _,
-        callback_ptr.cast(),
-        Some(timer_callback),
+        data_ptr.cast(),
+        Some(timer_callback::<D, F>),
       );
-      assert!(!ptr.is_null());
 
-      Timer { handle: ptr, callback: Some(Box::from_raw(callback_ptr)), name: self.name }
+      let data: Box<dyn Send> = Box::from_raw(data_ptr);
+
+      if ptr.is_null() {
+        return Err(FreeRtosError::OutOfMemory)
+      }
+
+      Ok(Timer { handle: ptr, data: Some(data), name: self.name })
     }
   }
 
+  /// Create the dynamic [`Timer`] with a context which is passed to every invocation of `callback`.
+  ///
+  /// The context is owned by the timer and dropped together with it. To share mutable state
+  /// with other tasks, use e.g. an `Arc<Mutex<T>>` as the context.
+  ///
+  /// Note that the newly created timer must be started.
+  ///
+  /// # Errors
+  ///
+  /// See [`create`](TimerBuilder::create).
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// extern crate alloc;
+  /// use alloc::sync::Arc;
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::{sync::Mutex, Timer};
+  ///
+  /// let counter = Arc::new(Mutex::new(0u32));
+  ///
+  /// let timer = Timer::new()
+  ///   .period(Duration::from_millis(100))
+  ///   .create_with_context(Arc::clone(&counter), |_timer, counter| {
+  ///     *counter.lock().unwrap() += 1;
+  ///   })
+  ///   .unwrap();
+  ///
+  /// timer.start(Duration::MAX).unwrap();
+  /// ```
+  #[cfg(freertos_feature = "dynamic_allocation")]
+  pub fn create_with_context<C, F>(self, context: C, callback: F) -> Result<Timer<'n>, FreeRtosError>
+  where
+    C: Send + 'static,
+    F: Fn(&TimerHandle, &C) + Send + 'static,
+  {
+    self.create(move |timer| callback(timer, &context))
+  }
+
   /// Create the static [`Timer`].
   ///
   /// Note that the newly created timer must be started.
   ///
+  /// # Errors
+  ///
+  /// Returns [`FreeRtosError::InvalidParameter`] if the period is shorter than a single tick.
+  ///
   /// # Safety
   ///
   /// The returned timer must have a `'static` lifetime.
@@ -108,12 +260,14 @@ impl<'n> TimerBuilder<'n> {
   /// }
   ///
   /// static mut TIMER: MaybeUninit<StaticTimer> = MaybeUninit::uninit();
-  /// let timer = Timer::new().period(Duration::from_millis(200)).create_static(unsafe { &mut TIMER }, my_timer_callback);
+  /// let timer = Timer::new().period(Duration::from_millis(200)).create_static(unsafe { &mut TIMER }, my_timer_callback).unwrap();
   ///
   /// timer.start(Duration::MAX);
   /// ```
   #[cfg(freertos_feature = "static_allocation")]
-  pub fn create_static(self, timer: &'static mut MaybeUninit<StaticTimer>, callback: fn(timer: &TimerHandle)) -> Timer<'n> {
+  pub fn create_static(self, timer: &'static mut MaybeUninit<StaticTimer>, callback: fn(timer: &TimerHandle)) -> Result<Timer<'n>, FreeRtosError> {
+    let period = self.period_ticks()?;
+
     extern "C" fn timer_callback(ptr: TimerHandle_t) {
       unsafe {
         let handle = TimerHandle::from_ptr(ptr);
@@ -137,7 +291,7 @@ impl<'n> TimerBuilder<'n> {
     unsafe {
       let ptr = xTimerCreateStatic(
         name,
-        self.period.ticks,
+        period.ticks,
         if self.auto_reload { pdTRUE } else { pdFALSE } as _,
         callback_ptr,
         Some(timer_callback),
@@ -147,12 +301,26 @@ impl<'n> TimerBuilder<'n> {
       debug_assert!(!ptr.is_null());
       debug_assert_eq!(ptr, ptr::addr_of_mut!((*timer_ptr).data) as TimerHandle_t);
 
-      Timer {
+      Ok(Timer {
         handle: ptr,
         #[cfg(freertos_feature = "dynamic_allocation")]
-        callback: None,
+        data: None,
         name: self.name
-      }
+      })
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sub_tick_period() {
+    let tick = Duration::from(Ticks::new(1));
+
+    assert_eq!(Timer::new().period(tick - Duration::from_nanos(1)).period_ticks(), Err(FreeRtosError::InvalidParameter));
+    assert_eq!(Timer::new().period(tick).period_ticks(), Ok(Ticks::new(1)));
+    assert_eq!(Timer::new().period(Ticks::new(3)).period_ticks(), Ok(Ticks::new(3)));
+  }
+}
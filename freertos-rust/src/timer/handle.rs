@@ -7,11 +7,14 @@ use crate::Ticks;
 use crate::ffi::Pointee;
 use crate::ffi::TimerHandle_t;
 use crate::shim::pcTimerGetName;
+use crate::shim::pvTimerGetTimerID;
 use crate::shim::pdFALSE;
 use crate::shim::pdPASS;
 use crate::shim::xTimerChangePeriod;
 use crate::shim::xTimerChangePeriodFromISR;
 use crate::shim::xTimerIsTimerActive;
+use crate::shim::xTimerReset;
+use crate::shim::xTimerResetFromISR;
 use crate::shim::xTimerStart;
 use crate::shim::xTimerStartFromISR;
 use crate::shim::xTimerStop;
@@ -57,6 +60,32 @@ impl TimerHandle {
     }
   }
 
+  /// Get the ID of the timer.
+  ///
+  /// # Safety
+  ///
+  /// The timer must have been created using [`create_with_id`](crate::timer::TimerBuilder::create_with_id)
+  /// with an ID of type `D`. Timers created using [`create`](crate::timer::TimerBuilder::create)
+  /// or [`create_mut`](crate::timer::TimerBuilder::create_mut) have an ID of type `()`.
+  #[cfg(freertos_feature = "dynamic_allocation")]
+  #[inline]
+  pub unsafe fn id<D>(&self) -> &D {
+    &*pvTimerGetTimerID(self.as_ptr()).cast::<D>()
+  }
+
+  /// Replace the ID of the timer, dropping the previous one.
+  ///
+  /// # Safety
+  ///
+  /// - The timer must have an ID of type `D`, see [`id`](TimerHandle::id).
+  /// - No references returned by [`id`](TimerHandle::id) may exist and the
+  ///   callback must not be running while the ID is replaced.
+  #[cfg(freertos_feature = "dynamic_allocation")]
+  #[inline]
+  pub unsafe fn set_id<D>(&self, id: D) {
+    drop(ptr::replace(pvTimerGetTimerID(self.as_ptr()).cast::<D>(), id))
+  }
+
   /// Check if the timer is active.
   #[inline]
   pub fn is_active(&self) -> bool {
@@ -99,6 +128,53 @@ impl TimerHandle {
     }
   }
 
+  /// Reset the timer.
+  ///
+  /// This restarts the timer so that it expires one period after this call. If the
+  /// timer is not active, it is started.
+  ///
+  /// # Examples
+  ///
+  /// An inactivity timer which fires if no event was received for a second:
+  ///
+  /// ```no_run
+  /// use core::time::Duration;
+  ///
+  /// use freertos_rust::Timer;
+  ///
+  /// let inactivity = Timer::new()
+  ///   .period(Duration::from_secs(1))
+  ///   .auto_reload(false)
+  ///   .create(|_timer| {
+  ///     println!("No events received.");
+  ///   })
+  ///   .unwrap();
+  ///
+  /// inactivity.start(Duration::MAX).unwrap();
+  ///
+  /// # fn wait_for_event() {}
+  /// loop {
+  ///   wait_for_event();
+  ///   inactivity.reset(Duration::MAX).unwrap();
+  /// }
+  /// ```
+  #[inline]
+  pub fn reset(&self, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError> {
+    match unsafe { xTimerReset(self.as_ptr(), timeout.into().into()) } {
+      pdPASS => Ok(()),
+      _ => Err(FreeRtosError::Timeout),
+    }
+  }
+
+  /// Reset the timer from an interrupt service routine.
+  #[inline]
+  pub fn reset_from_isr(&self, ic: &InterruptContext) -> Result<(), FreeRtosError> {
+    match unsafe { xTimerResetFromISR(self.as_ptr(), ic.as_ptr()) } {
+      pdPASS => Ok(()),
+      _ => Err(FreeRtosError::Timeout),
+    }
+  }
+
   /// Get the timer's period.
   #[cfg(freertos_feature = "timer_get_period")]
   pub fn period(&self) -> Ticks {
@@ -106,6 +182,27 @@ impl TimerHandle {
     Ticks::new(unsafe { xTimerGetPeriod(self.as_ptr()) })
   }
 
+  /// Get the tick count at which the timer will expire.
+  ///
+  /// This is only meaningful while the timer is [active](TimerHandle::is_active).
+  #[cfg(freertos_feature = "timer_get_expiry_time")]
+  #[inline]
+  pub fn expiry_time(&self) -> Ticks {
+    use crate::shim::xTimerGetExpiryTime;
+    Ticks::new(unsafe { xTimerGetExpiryTime(self.as_ptr()) })
+  }
+
+  /// Get the number of ticks remaining until the timer expires.
+  ///
+  /// This correctly handles the tick count overflowing before the timer expires.
+  /// Like [`expiry_time`](TimerHandle::expiry_time), this is only meaningful while
+  /// the timer is [active](TimerHandle::is_active).
+  #[cfg(freertos_feature = "timer_get_expiry_time")]
+  #[inline]
+  pub fn remaining(&self) -> Ticks {
+    self.expiry_time().wrapping_sub(crate::task::Scheduler::tick_count())
+  }
+
   /// Change the timer's period.
   #[inline]
   pub fn change_period(
@@ -1,21 +1,31 @@
 //! FreeRTOS timer primitives.
 
-use core::{ffi::CStr, ops::Deref};
+use core::{ffi::CStr, ops::Deref, time::Duration};
 
 #[cfg(freertos_feature = "dynamic_allocation")]
 use alloc2::boxed::Box;
 
 use crate::shim::*;
-use crate::ticks::Ticks;
-use crate::task::TaskHandle;
+use crate::task::{TaskHandle, TaskPriority};
 
 mod builder;
 pub use builder::TimerBuilder;
 mod handle;
 pub use handle::TimerHandle;
+#[cfg(all(freertos_feature = "dynamic_allocation", freertos_feature = "timer_pend_function_call"))]
+mod pend;
+#[cfg(all(freertos_feature = "dynamic_allocation", freertos_feature = "timer_pend_function_call"))]
+pub use pend::{pend_function_call, pend_function_call_from_isr};
 
+/// Heap block referenced by the ID of a dynamic timer.
+///
+/// The ID comes first, so it can be accessed without knowing the type of the callback.
 #[cfg(freertos_feature = "dynamic_allocation")]
-type BoxTimerFn = Box<dyn Fn(&TimerHandle)>;
+#[repr(C)]
+struct TimerData<D, F> {
+  id: D,
+  callback: F,
+}
 
 /// A software timer.
 ///
@@ -27,7 +37,7 @@ pub struct Timer<'n> {
   handle: TimerHandle_t,
   #[cfg(freertos_feature = "dynamic_allocation")]
   #[allow(unused)]
-  callback: Option<Box<BoxTimerFn>>,
+  data: Option<Box<dyn Send>>,
   #[allow(unused)]
   name: Option<&'n CStr>,
 }
@@ -39,6 +49,20 @@ impl<'n> Timer<'n> {
   /// Stack size of the timer daemon task.
   pub const STACK_SIZE: u16 = configTIMER_TASK_STACK_DEPTH;
 
+  /// Get the priority of the timer daemon task, i.e. `configTIMER_TASK_PRIORITY`.
+  #[inline]
+  pub const fn daemon_priority() -> TaskPriority {
+    unsafe { TaskPriority::new_unchecked(configTIMER_TASK_PRIORITY as u8) }
+  }
+
+  /// Get the length of the timer command queue, i.e. `configTIMER_QUEUE_LENGTH`.
+  ///
+  /// Every timer operation sends a command through this queue to the timer daemon task.
+  #[inline]
+  pub const fn command_queue_length() -> usize {
+    configTIMER_QUEUE_LENGTH as usize
+  }
+
   /// Get the handle for the timer daemon task.
   #[inline]
   pub fn daemon_task() -> &'static TaskHandle {
@@ -53,7 +77,7 @@ impl<'n> Timer<'n> {
   pub const fn new() -> TimerBuilder<'static> {
     TimerBuilder {
       name: None,
-      period: Ticks::new(0),
+      period: Duration::ZERO,
       auto_reload: true,
     }
   }
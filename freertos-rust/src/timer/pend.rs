@@ -0,0 +1,68 @@
+use core::ffi::c_void;
+
+use alloc2::boxed::Box;
+
+use crate::{
+  shim::{pdPASS, xTimerPendFunctionCall, xTimerPendFunctionCallFromISR},
+  FreeRtosError,
+  InterruptContext,
+  Ticks,
+};
+
+extern "C" fn trampoline<F: FnOnce() + Send + 'static>(param: *mut c_void, _: u32) {
+  let f = unsafe { Box::from_raw(param.cast::<F>()) };
+  f();
+}
+
+/// Defer the execution of `f` to the timer daemon task.
+///
+/// This requires `INCLUDE_xTimerPendFunctionCall`.
+///
+/// # Errors
+///
+/// Returns [`FreeRtosError::Timeout`] if the timer command queue was full
+/// and did not have space before the timeout expired.
+///
+/// # Examples
+///
+/// ```no_run
+/// use core::time::Duration;
+///
+/// use freertos_rust::timer::pend_function_call;
+///
+/// pend_function_call(|| println!("Running on the timer daemon task."), Duration::MAX).unwrap();
+/// ```
+pub fn pend_function_call<F>(f: F, timeout: impl Into<Ticks>) -> Result<(), FreeRtosError>
+where
+  F: FnOnce() + Send + 'static,
+{
+  let param = Box::into_raw(Box::new(f));
+
+  match unsafe { xTimerPendFunctionCall(Some(trampoline::<F>), param.cast(), 0, timeout.into().into()) } {
+    pdPASS => Ok(()),
+    _ => {
+      drop(unsafe { Box::from_raw(param) });
+      Err(FreeRtosError::Timeout)
+    },
+  }
+}
+
+/// Defer the execution of `f` to the timer daemon task from an interrupt service routine.
+///
+/// Since allocating or freeing memory is not allowed in an interrupt service routine,
+/// `f` must already be boxed and is only freed on the timer daemon task after it has run.
+/// If the timer command queue is full, `f` is returned so it can be dropped outside of
+/// the interrupt service routine.
+///
+/// This requires `INCLUDE_xTimerPendFunctionCall`.
+pub fn pend_function_call_from_isr<F>(f: Box<F>, ic: &InterruptContext) -> Result<(), Box<F>>
+where
+  F: FnOnce() + Send + 'static,
+{
+  let param = Box::into_raw(f);
+
+  match unsafe { xTimerPendFunctionCallFromISR(Some(trampoline::<F>), param.cast(), 0, ic.as_ptr()) } {
+    pdPASS => Ok(()),
+    _ => Err(unsafe { Box::from_raw(param) }),
+  }
+}
@@ -1,7 +1,21 @@
-use crate::shim::freertos_rs_get_configCPU_CLOCK_HZ;
+use crate::shim::{configTICK_RATE_HZ, freertos_rs_get_configCPU_CLOCK_HZ, portTICK_PERIOD_MS};
 
 /// Get the CPU frequency in Hertz.
 #[inline(always)]
 pub fn cpu_clock_hz() -> usize {
   unsafe { freertos_rs_get_configCPU_CLOCK_HZ() as _ }
 }
+
+/// Get the tick frequency in Hertz, i.e. `configTICK_RATE_HZ`.
+#[inline(always)]
+pub const fn tick_rate_hz() -> u32 {
+  configTICK_RATE_HZ as u32
+}
+
+/// Get the tick period in milliseconds, i.e. `portTICK_PERIOD_MS`.
+///
+/// This is `0` if `configTICK_RATE_HZ` is greater than `1000`.
+#[inline(always)]
+pub const fn tick_period_ms() -> u32 {
+  portTICK_PERIOD_MS as u32
+}